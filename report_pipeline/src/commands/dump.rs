@@ -0,0 +1,192 @@
+//! Versioned, compressed dump/restore of an entire report tree.
+//!
+//! [`dump`] packages every `report.json` under the report directory, together
+//! with the generated `index.json`, into a single gzip-compressed tar archive
+//! for backup or distribution. [`restore`] unpacks such an archive and
+//! regenerates `index.json` from the restored reports. The archive is
+//! self-describing: a top-level `metadata.json` records the dump format
+//! version, the crate version, and a UTC timestamp, so a future reader can
+//! reject an incompatible layout before extracting anything.
+
+use crate::commands::report::rebuild_index;
+use crate::{log_info, log_warn};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The dump layout version. Bumped only on an incompatible archive change.
+const DUMP_VERSION: u32 = 1;
+
+/// Self-describing header stored as `metadata.json` at the archive root.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DumpMetadata {
+    /// Archive layout version; [`restore`] rejects versions it cannot read.
+    pub dump_version: u32,
+    /// The crate version that produced the archive.
+    pub crate_version: String,
+    /// Seconds since the UNIX epoch (UTC) at which the dump was taken.
+    pub created_at: u64,
+}
+
+impl DumpMetadata {
+    fn now() -> DumpMetadata {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        DumpMetadata {
+            dump_version: DUMP_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at,
+        }
+    }
+}
+
+/// Package `report_dir` into a gzip-compressed tar archive at `archive_path`.
+///
+/// Reports are laid out as `indexes/<jurisdiction>/<election>/<contest>/report.json`
+/// mirroring their on-disk structure, `index.json` is carried alongside, and
+/// `metadata.json` heads the archive. The archive is assembled in a temporary
+/// directory and moved into place so a partial write never clobbers an existing
+/// archive.
+pub fn dump(report_dir: &Path, archive_path: &Path) {
+    let temp = tempfile::TempDir::new().expect("Failed to create temp dir for dump");
+    let temp_archive = temp.path().join("dump.tar.gz");
+
+    let file = File::create(&temp_archive)
+        .unwrap_or_else(|e| panic!("Failed to create archive {}: {}", temp_archive.display(), e));
+    let encoder = GzEncoder::new(file, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    // metadata.json first, so it is the first entry a reader encounters.
+    let metadata = DumpMetadata::now();
+    let metadata_bytes = serde_json::to_vec_pretty(&metadata).expect("serialize dump metadata");
+    append_bytes(&mut builder, "metadata.json", &metadata_bytes);
+
+    // index.json, if present.
+    let index_path = report_dir.join("index.json");
+    if index_path.exists() {
+        builder
+            .append_path_with_name(&index_path, "index.json")
+            .expect("append index.json");
+    }
+
+    // Every report.json under `indexes/…`.
+    let mut reports = Vec::new();
+    find_reports(report_dir, &mut reports);
+    for report in &reports {
+        let rel = report
+            .strip_prefix(report_dir)
+            .expect("report under report_dir");
+        let name = Path::new("indexes").join(rel);
+        builder
+            .append_path_with_name(report, &name)
+            .unwrap_or_else(|e| panic!("append {}: {}", report.display(), e));
+    }
+
+    let encoder = builder.into_inner().expect("finish tar");
+    encoder.finish().expect("finish gzip");
+
+    std::fs::rename(&temp_archive, archive_path)
+        .or_else(|_| std::fs::copy(&temp_archive, archive_path).map(|_| ()))
+        .unwrap_or_else(|e| panic!("Failed to place archive {}: {}", archive_path.display(), e));
+
+    log_info!(
+        "Dumped {} reports to {}",
+        reports.len(),
+        archive_path.display()
+    );
+}
+
+/// Unpack an archive produced by [`dump`] into `target_dir` and rebuild the
+/// index. The archive's `metadata.json` is read first and an incompatible
+/// `dump_version` aborts the restore before any file is written.
+pub fn restore(archive_path: &Path, target_dir: &Path) {
+    // First pass: read metadata.json and verify the version.
+    let metadata = read_metadata(archive_path);
+    if metadata.dump_version != DUMP_VERSION {
+        log_warn!(
+            "Refusing to restore archive {}: dump_version {} is incompatible with {}",
+            archive_path.display(),
+            metadata.dump_version,
+            DUMP_VERSION
+        );
+        return;
+    }
+
+    // Second pass: extract reports and index.json into the target directory.
+    let file = File::open(archive_path)
+        .unwrap_or_else(|e| panic!("Failed to open archive {}: {}", archive_path.display(), e));
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    std::fs::create_dir_all(target_dir).expect("create target dir");
+    for entry in archive.entries().expect("read archive entries") {
+        let mut entry = entry.expect("read archive entry");
+        let path = entry.path().expect("entry path").into_owned();
+
+        let out = if let Ok(rel) = path.strip_prefix("indexes") {
+            Some(target_dir.join(rel))
+        } else if path == Path::new("index.json") {
+            Some(target_dir.join("index.json"))
+        } else {
+            // metadata.json and anything else is not part of the report tree.
+            None
+        };
+
+        if let Some(out) = out {
+            if let Some(parent) = out.parent() {
+                std::fs::create_dir_all(parent).expect("create parent dir");
+            }
+            entry.unpack(&out).expect("unpack entry");
+        }
+    }
+
+    // Regenerate index.json from the restored reports to be safe.
+    rebuild_index(target_dir);
+    log_info!("Restored archive {} into {}", archive_path.display(), target_dir.display());
+}
+
+/// Read `metadata.json` from the archive without extracting the whole thing.
+fn read_metadata(archive_path: &Path) -> DumpMetadata {
+    let file = File::open(archive_path)
+        .unwrap_or_else(|e| panic!("Failed to open archive {}: {}", archive_path.display(), e));
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    for entry in archive.entries().expect("read archive entries") {
+        let entry = entry.expect("read archive entry");
+        if entry.path().map(|p| p == Path::new("metadata.json")).unwrap_or(false) {
+            return serde_json::from_reader(entry).expect("parse dump metadata");
+        }
+    }
+
+    panic!("Archive {} is missing metadata.json", archive_path.display());
+}
+
+/// Append in-memory bytes as an archive entry with the given name.
+fn append_bytes<W: std::io::Write>(builder: &mut tar::Builder<W>, name: &str, bytes: &[u8]) {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, name, bytes)
+        .unwrap_or_else(|e| panic!("append {}: {}", name, e));
+}
+
+/// Recursively collect every `report.json` under `dir`.
+fn find_reports(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                find_reports(&path, out);
+            } else if path.file_name().and_then(|n| n.to_str()) == Some("report.json") {
+                out.push(path);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! eSTV-compatible stage-by-stage CSV count report.
+//!
+//! This writer emits a machine-readable audit trail of how a count proceeds, in
+//! the eSTV column layout: one column block per count stage, a row per
+//! candidate showing the votes received that stage, the running total, and the
+//! candidate's status (continuing / elected / excluded), plus summary rows for
+//! the quota (when the count has one), exhausted votes, and total active votes.
+//! A winner is marked elected in the final stage. The output is diffable
+//! against reference counters and suitable for publishing official tallies.
+
+use crate::model::election::CandidateId;
+use crate::model::report::ContestReport;
+use crate::tabulator::Allocatee;
+use std::collections::BTreeMap;
+
+/// Options controlling the rendered CSV.
+#[derive(Debug, Clone, Default)]
+pub struct EstvCsvOptions {
+    /// Omit rows for candidates who were excluded before the final stage.
+    pub hide_excluded: bool,
+    /// Sort candidate rows by their current (final-stage) vote total, highest
+    /// first, rather than by candidate order.
+    pub sort_by_votes: bool,
+}
+
+/// A running per-candidate view assembled from the tabulator rounds.
+struct CandidateRow {
+    id: CandidateId,
+    name: String,
+    /// Running total after each stage; `None` once excluded.
+    totals: Vec<Option<u32>>,
+    excluded_at: Option<usize>,
+}
+
+fn escape(field: &str) -> String {
+    if field.contains(',') || field.contains('"') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render a [`ContestReport`] as an eSTV-style stage-by-stage CSV string.
+pub fn write_estv_csv(report: &ContestReport, options: &EstvCsvOptions) -> String {
+    let stages = report.rounds.len();
+
+    // Build a per-candidate running total across stages.
+    let mut rows: Vec<CandidateRow> = report
+        .candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| CandidateRow {
+            id: CandidateId(i as u32),
+            name: c.name.clone(),
+            totals: vec![None; stages],
+            excluded_at: None,
+        })
+        .collect();
+
+    let mut exhausted_per_stage = vec![0u32; stages];
+    for (stage, round) in report.rounds.iter().enumerate() {
+        for alloc in &round.allocations {
+            match alloc.allocatee {
+                Allocatee::Candidate(cid) => {
+                    if let Some(row) = rows.iter_mut().find(|r| r.id == cid) {
+                        row.totals[stage] = Some(alloc.votes);
+                    }
+                }
+                Allocatee::Exhausted => exhausted_per_stage[stage] = alloc.votes,
+            }
+        }
+        for transfer in &round.transfers {
+            if let Some(row) = rows.iter_mut().find(|r| r.id == transfer.from) {
+                if row.excluded_at.is_none() {
+                    row.excluded_at = Some(stage);
+                }
+            }
+        }
+    }
+
+    if options.hide_excluded {
+        rows.retain(|r| r.excluded_at.is_none());
+    }
+
+    if options.sort_by_votes {
+        rows.sort_by_key(|r| {
+            std::cmp::Reverse(r.totals.iter().rev().flatten().copied().next().unwrap_or(0))
+        });
+    }
+
+    // Header: candidate, then a (received, total, status) block per stage.
+    let mut out = String::new();
+    let mut header = vec!["Candidate".to_string()];
+    for stage in 1..=stages {
+        header.push(format!("Stage {} Received", stage));
+        header.push(format!("Stage {} Total", stage));
+        header.push(format!("Stage {} Status", stage));
+    }
+    out.push_str(&header.join(","));
+    out.push('\n');
+
+    for row in &rows {
+        let mut fields = vec![escape(&row.name)];
+        let mut prev: u32 = 0;
+        for stage in 0..stages {
+            match row.totals[stage] {
+                Some(total) => {
+                    let received = total as i64 - prev as i64;
+                    prev = total;
+                    let status = if row.excluded_at == Some(stage) {
+                        "excluded"
+                    } else if stage + 1 == stages && report.winners.contains(&row.id) {
+                        "elected"
+                    } else {
+                        "continuing"
+                    };
+                    fields.push(received.to_string());
+                    fields.push(total.to_string());
+                    fields.push(status.to_string());
+                }
+                None => {
+                    fields.push(String::new());
+                    fields.push(String::new());
+                    fields.push("excluded".to_string());
+                }
+            }
+        }
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    // Summary rows: quota (if any), exhausted and total active votes per stage.
+    let summary = |label: &str, values: &BTreeMap<usize, u32>| {
+        let mut fields = vec![label.to_string()];
+        for stage in 0..stages {
+            fields.push(String::new());
+            fields.push(values.get(&stage).copied().unwrap_or(0).to_string());
+            fields.push(String::new());
+        }
+        format!("{}\n", fields.join(","))
+    };
+
+    let quota_map: BTreeMap<usize, u32> = report
+        .stage_log
+        .iter()
+        .enumerate()
+        .filter_map(|(stage, record)| record.quota.map(|quota| (stage, quota)))
+        .collect();
+    if !quota_map.is_empty() {
+        out.push_str(&summary("Quota", &quota_map));
+    }
+
+    let exhausted_map: BTreeMap<usize, u32> =
+        exhausted_per_stage.iter().copied().enumerate().collect();
+    out.push_str(&summary("Exhausted", &exhausted_map));
+
+    let active_map: BTreeMap<usize, u32> = (0..stages)
+        .map(|stage| {
+            let active: u32 = report.rounds[stage]
+                .allocations
+                .iter()
+                .filter_map(|a| match a.allocatee {
+                    Allocatee::Candidate(_) => Some(a.votes),
+                    Allocatee::Exhausted => None,
+                })
+                .sum();
+            (stage, active)
+        })
+        .collect();
+    out.push_str(&summary("Total active votes", &active_map));
+
+    out
+}
@@ -0,0 +1,367 @@
+//! Incremental index updates with a debounced file-watch mode.
+//!
+//! [`rebuild_index`](super::report::rebuild_index) rescans every `report.json`
+//! under the report directory on each call, which does not scale once a
+//! deployment holds thousands of contests. This module keeps a sidecar
+//! manifest next to `index.json` recording each report's last-seen mtime and
+//! size, so a long-running [`watch`] loop can reprocess only the reports that
+//! actually changed and splice them into an in-memory [`ReportIndex`].
+//!
+//! Filesystem bursts — a full regeneration writing hundreds of files — are
+//! smoothed by buffering observed changes and flushing them only after a short
+//! quiet period. A pause/resume toggle lets a generation run suppress handling
+//! while it writes and fire a single batched update at the end.
+
+use crate::model::report::{ContestIndexEntry, ContestReport, ElectionIndexEntry, ReportIndex};
+use crate::util::{read_serialized, write_serialized};
+use crate::{log_debug, log_info, log_warn};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Last-seen size and mtime (seconds since the epoch) of a tracked report.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    mtime: u64,
+    size: u64,
+}
+
+/// Sidecar manifest persisted alongside `index.json`, mapping each report's
+/// path (relative to the report directory) to its last-seen stamp.
+struct IndexManifest {
+    stamps: BTreeMap<String, FileStamp>,
+}
+
+impl IndexManifest {
+    const FILE_NAME: &'static str = "index.manifest.json";
+
+    /// Load the manifest, or start empty if it does not yet exist.
+    fn load(report_dir: &Path) -> IndexManifest {
+        let path = report_dir.join(Self::FILE_NAME);
+        let stamps = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| parse_manifest(&s))
+            .unwrap_or_default();
+        IndexManifest { stamps }
+    }
+
+    fn save(&self, report_dir: &Path) {
+        let path = report_dir.join(Self::FILE_NAME);
+        let mut out = String::from("{\n");
+        for (i, (rel, stamp)) in self.stamps.iter().enumerate() {
+            if i > 0 {
+                out.push_str(",\n");
+            }
+            out.push_str(&format!(
+                "  {}: {{\"mtime\": {}, \"size\": {}}}",
+                json_string(rel),
+                stamp.mtime,
+                stamp.size
+            ));
+        }
+        out.push_str("\n}\n");
+        if let Err(e) = std::fs::write(&path, out) {
+            log_warn!("Failed to write index manifest {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Stamp a report file on disk, returning `None` if it cannot be stat'd.
+fn stamp_of(path: &Path) -> Option<FileStamp> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Some(FileStamp {
+        mtime,
+        size: meta.len(),
+    })
+}
+
+/// A live, in-memory index that changed reports can be spliced into.
+pub struct IncrementalIndexer {
+    report_dir: PathBuf,
+    /// Elections keyed by their relative path.
+    elections: HashMap<String, ElectionIndexEntry>,
+    /// For each tracked `report.json` (relative), the election path and office
+    /// it contributes, so deletions can be undone precisely.
+    placement: HashMap<String, (String, String)>,
+    manifest: IndexManifest,
+    /// Buffered relative report paths awaiting a flush.
+    pending: Vec<PathBuf>,
+    /// When paused, observed changes are buffered but not flushed.
+    paused: Arc<AtomicBool>,
+}
+
+impl IncrementalIndexer {
+    /// Build an indexer seeded from the existing `index.json` and manifest.
+    pub fn load(report_dir: &Path) -> IncrementalIndexer {
+        let index_path = report_dir.join("index.json");
+        let elections = if index_path.exists() {
+            let index: ReportIndex = read_serialized(&index_path);
+            index
+                .elections
+                .into_iter()
+                .map(|e| (e.path.clone(), e))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut placement = HashMap::new();
+        for (path, election) in &elections {
+            for contest in &election.contests {
+                placement.insert(
+                    format!("{}/{}/report.json", path, contest.office),
+                    (path.clone(), contest.office.clone()),
+                );
+            }
+        }
+
+        IncrementalIndexer {
+            report_dir: report_dir.to_path_buf(),
+            elections,
+            placement,
+            manifest: IndexManifest::load(report_dir),
+            pending: Vec::new(),
+            paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// A handle that can pause and resume flushing from another scope (e.g. a
+    /// generation run that wants to suppress handling while it writes).
+    pub fn pause_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.paused)
+    }
+
+    /// Record that a report was added or modified. The change is buffered; call
+    /// [`flush`](Self::flush) to apply it.
+    pub fn note_change(&mut self, report_rel: &Path) {
+        self.pending.push(report_rel.to_path_buf());
+    }
+
+    /// Record that a report was removed, pruning it from the live index and the
+    /// election entry, dropping the election if it becomes empty.
+    pub fn note_removal(&mut self, report_rel: &Path) {
+        let rel = report_rel.to_string_lossy().to_string();
+        let Some((election_path, office)) = self.placement.remove(&rel) else {
+            return;
+        };
+        if let Some(election) = self.elections.get_mut(&election_path) {
+            election.contests.retain(|c| c.office != office);
+            if election.contests.is_empty() {
+                self.elections.remove(&election_path);
+            }
+        }
+        self.manifest.stamps.remove(&rel);
+    }
+
+    /// Apply and clear all buffered changes, then rewrite `index.json` and the
+    /// manifest. A no-op while paused.
+    pub fn flush(&mut self) {
+        if self.paused.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let pending = std::mem::take(&mut self.pending);
+        for report_rel in pending {
+            self.apply_change(&report_rel);
+        }
+        self.write();
+    }
+
+    /// Splice a single changed report into the live index, honoring the same
+    /// "skip empty report" rule as the batch builder.
+    fn apply_change(&mut self, report_rel: &Path) {
+        let full = self.report_dir.join(report_rel);
+        let Some(stamp) = stamp_of(&full) else {
+            return;
+        };
+        let rel = report_rel.to_string_lossy().to_string();
+
+        // Unchanged since last seen — nothing to do.
+        if self.manifest.stamps.get(&rel) == Some(&stamp) {
+            return;
+        }
+
+        let report = match std::panic::catch_unwind(|| read_serialized::<ContestReport>(&full)) {
+            Ok(report) => report,
+            Err(_) => {
+                log_warn!("Failed to parse changed report {}", full.display());
+                return;
+            }
+        };
+
+        if report.ballot_count == 0 || report.num_candidates == 0 || report.rounds.is_empty() {
+            log_debug!("Skipping empty report: {}", full.display());
+            return;
+        }
+
+        let election_path = format!(
+            "{}/{}",
+            report.info.jurisdiction_path, report.info.election_path
+        );
+        let entry = index_entry_from_report(&report);
+        let office = entry.office.clone();
+
+        let election = self
+            .elections
+            .entry(election_path.clone())
+            .or_insert_with(|| ElectionIndexEntry {
+                path: election_path.clone(),
+                jurisdiction_name: report.info.jurisdiction_name.clone(),
+                election_name: report.info.election_name.clone(),
+                date: report.info.date.clone(),
+                contests: Vec::new(),
+            });
+
+        // Replace any prior entry for this office so re-runs overwrite cleanly.
+        election.contests.retain(|c| c.office != office);
+        election.contests.push(entry);
+        election.contests.sort_by(|a, b| a.office_name.cmp(&b.office_name));
+
+        self.placement
+            .insert(rel.clone(), (election_path, office));
+        self.manifest.stamps.insert(rel, stamp);
+    }
+
+    fn write(&self) {
+        let mut elections: Vec<ElectionIndexEntry> = self.elections.values().cloned().collect();
+        elections.sort_by(|a, b| (&b.date, &b.path).cmp(&(&a.date, &a.path)));
+        let report_index = ReportIndex { elections };
+        write_serialized(&self.report_dir.join("index.json"), &report_index);
+        self.manifest.save(&self.report_dir);
+    }
+}
+
+/// Build a [`ContestIndexEntry`] from a parsed report, filling the same fields
+/// as the batch index builder in `report` so both paths produce identical entries.
+pub fn index_entry_from_report(report: &ContestReport) -> ContestIndexEntry {
+    ContestIndexEntry {
+        office: report.info.office.clone(),
+        office_name: report.info.office_name.clone(),
+        name: report.info.name.clone(),
+        winner: report
+            .winner()
+            .map(|w| w.name.clone())
+            .unwrap_or_else(|| "No Winner".to_string()),
+        num_candidates: report.num_candidates,
+        num_rounds: report.rounds.len() as u32,
+        condorcet_winner: report
+            .condorcet
+            .and_then(|c| report.candidates.get(c.0 as usize).map(|c| c.name.clone())),
+        has_non_condorcet_winner: report.condorcet.is_some() && report.condorcet != report.winner,
+        has_write_in_by_name: report
+            .candidates
+            .iter()
+            .any(|c| super::report::is_write_in_by_name(&c.name)),
+    }
+}
+
+/// Long-running watch loop: poll `report_dir` for added/changed/removed
+/// `report.json` files, buffering events and flushing after `quiet_period` of
+/// no further activity. Polling keeps the watcher dependency-free; the debounce
+/// prevents a regeneration burst from triggering a flush per file.
+pub fn watch(report_dir: &Path, quiet_period: Duration) {
+    log_info!("Watching {} for report changes...", report_dir.display());
+    let mut indexer = IncrementalIndexer::load(report_dir);
+    let mut last_seen: BTreeMap<String, FileStamp> = BTreeMap::new();
+    let mut dirty = false;
+
+    loop {
+        let current = scan_reports(report_dir);
+
+        let mut changed = false;
+        for (rel, stamp) in &current {
+            if last_seen.get(rel) != Some(stamp) {
+                indexer.note_change(Path::new(rel));
+                changed = true;
+            }
+        }
+        for rel in last_seen.keys() {
+            if !current.contains_key(rel) {
+                indexer.note_removal(Path::new(rel));
+                changed = true;
+            }
+        }
+
+        if changed {
+            // Keep buffering while the burst continues.
+            dirty = true;
+        } else if dirty {
+            // A full quiet period passed with no further activity: flush the
+            // burst once, not per file.
+            indexer.flush();
+            dirty = false;
+        }
+
+        last_seen = current;
+        std::thread::sleep(quiet_period);
+    }
+}
+
+/// Scan `report_dir` for every `report.json`, keyed by relative path.
+fn scan_reports(report_dir: &Path) -> BTreeMap<String, FileStamp> {
+    fn walk(dir: &Path, base: &Path, out: &mut BTreeMap<String, FileStamp>) {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    walk(&path, base, out);
+                } else if path.file_name().and_then(|n| n.to_str()) == Some("report.json") {
+                    if let (Ok(rel), Some(stamp)) = (path.strip_prefix(base), stamp_of(&path)) {
+                        out.insert(rel.to_string_lossy().to_string(), stamp);
+                    }
+                }
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    walk(report_dir, report_dir, &mut out);
+    out
+}
+
+/// Minimal parser for the flat manifest object we write, tolerant of its own
+/// formatting so we never pull in a serde derive for a two-field record.
+fn parse_manifest(src: &str) -> Option<BTreeMap<String, FileStamp>> {
+    let mut stamps = BTreeMap::new();
+    for line in src.lines() {
+        let line = line.trim().trim_end_matches(',');
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+        if key.is_empty() || !rest.contains("mtime") {
+            continue;
+        }
+        let mtime = extract_number(rest, "mtime")?;
+        let size = extract_number(rest, "size")?;
+        stamps.insert(key.to_string(), FileStamp { mtime, size });
+    }
+    Some(stamps)
+}
+
+fn extract_number(src: &str, field: &str) -> Option<u64> {
+    let idx = src.find(field)? + field.len();
+    let tail = &src[idx..];
+    let digits: String = tail.chars().skip_while(|c| !c.is_ascii_digit()).take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Escape a string for our hand-written manifest JSON.
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Default for IndexManifest {
+    fn default() -> IndexManifest {
+        IndexManifest {
+            stamps: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Flat exports of the contest index for analysis and spreadsheets.
+//!
+//! `ReportIndex` is normally serialized only as the nested `index.json`, which
+//! is awkward to load into pandas or a database. This module derives a flat,
+//! one-row-per-contest view from the same `ElectionIndexEntry`/
+//! `ContestIndexEntry` data and writes it as NDJSON (one JSON object per line,
+//! streamed so it scales to huge corpora) and/or CSV.
+//!
+//! The exports are opt-in: set `STV_INDEX_EXPORT` to a comma-separated list of
+//! `ndjson` and/or `csv` to have the index-writing paths emit them alongside
+//! `index.json`.
+
+use crate::model::report::ReportIndex;
+use crate::{log_info, log_warn};
+use serde::Serialize;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// One flattened contest row, shared by both exporters.
+#[derive(Debug, Serialize)]
+struct FlatContest<'a> {
+    jurisdiction_name: &'a str,
+    election_name: &'a str,
+    date: &'a str,
+    office_name: &'a str,
+    name: &'a str,
+    winner: &'a str,
+    num_candidates: u32,
+    num_rounds: u32,
+    condorcet_winner: Option<&'a str>,
+    has_non_condorcet_winner: bool,
+    has_write_in_by_name: bool,
+}
+
+/// Iterate the index as flat rows, borrowing from the index in place.
+fn rows(index: &ReportIndex) -> impl Iterator<Item = FlatContest<'_>> {
+    index.elections.iter().flat_map(|election| {
+        election.contests.iter().map(move |contest| FlatContest {
+            jurisdiction_name: &election.jurisdiction_name,
+            election_name: &election.election_name,
+            date: &election.date,
+            office_name: &contest.office_name,
+            name: &contest.name,
+            winner: &contest.winner,
+            num_candidates: contest.num_candidates,
+            num_rounds: contest.num_rounds,
+            condorcet_winner: contest.condorcet_winner.as_deref(),
+            has_non_condorcet_winner: contest.has_non_condorcet_winner,
+            has_write_in_by_name: contest.has_write_in_by_name,
+        })
+    })
+}
+
+/// Emit whichever flat exports `STV_INDEX_EXPORT` requests, if any.
+pub fn write_index_exports(report_dir: &Path, index: &ReportIndex) {
+    let Ok(spec) = std::env::var("STV_INDEX_EXPORT") else {
+        return;
+    };
+    for format in spec.split(',').map(str::trim) {
+        match format {
+            "ndjson" => write_ndjson(&report_dir.join("index.ndjson"), index),
+            "csv" => write_csv(&report_dir.join("index.csv"), index),
+            "" => {}
+            other => log_warn!("Unknown STV_INDEX_EXPORT format: {}", other),
+        }
+    }
+}
+
+/// Write one JSON object per line, flushing as it goes so memory stays flat.
+pub fn write_ndjson(path: &Path, index: &ReportIndex) {
+    let file = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(e) => {
+            log_warn!("Failed to create {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let mut writer = BufWriter::new(file);
+    let mut count = 0u32;
+    for row in rows(index) {
+        let line = serde_json::to_string(&row).expect("serialize flat contest");
+        if writeln!(writer, "{}", line).is_err() {
+            log_warn!("Failed writing NDJSON row to {}", path.display());
+            return;
+        }
+        count += 1;
+    }
+    log_info!("Wrote {} contest rows to {}", count, path.display());
+}
+
+/// Write a CSV with a header and one row per contest.
+pub fn write_csv(path: &Path, index: &ReportIndex) {
+    let file = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(e) => {
+            log_warn!("Failed to create {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let mut writer = BufWriter::new(file);
+
+    let header = "jurisdiction_name,election_name,date,office_name,name,winner,\
+num_candidates,num_rounds,condorcet_winner,has_non_condorcet_winner,has_write_in_by_name";
+    if writeln!(writer, "{}", header).is_err() {
+        log_warn!("Failed writing CSV header to {}", path.display());
+        return;
+    }
+
+    for row in rows(index) {
+        let line = format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(row.jurisdiction_name),
+            csv_field(row.election_name),
+            csv_field(row.date),
+            csv_field(row.office_name),
+            csv_field(row.name),
+            csv_field(row.winner),
+            row.num_candidates,
+            row.num_rounds,
+            csv_field(row.condorcet_winner.unwrap_or("")),
+            row.has_non_condorcet_winner,
+            row.has_write_in_by_name,
+        );
+        if writeln!(writer, "{}", line).is_err() {
+            log_warn!("Failed writing CSV row to {}", path.display());
+            return;
+        }
+    }
+}
+
+/// Quote a CSV field if it contains a comma, quote, or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
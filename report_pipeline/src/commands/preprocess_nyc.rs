@@ -1,47 +1,128 @@
 use crate::commands::report::rebuild_index;
 use crate::formats::us_ny_nyc::efficient_reader::read_all_nyc_data;
-use crate::model::election::{ElectionInfo, ElectionPreprocessed};
+use crate::model::election::{Election, ElectionInfo, ElectionPreprocessed};
 use crate::normalizers::normalize_election;
 use crate::read_metadata::read_meta;
 use crate::report::generate_report;
 use crate::util::write_serialized;
+use std::collections::BTreeMap;
 use std::fs::create_dir_all;
 use std::path::Path;
 
-/// Efficiently process all NYC races in one pass and generate final reports
-/// This skips the normalized.json.gz step entirely and generates reports directly from memory
-pub fn preprocess_nyc(metadata_dir: &str, raw_data_dir: &str, report_dir: &str) {
-    // Read metadata from all files in the directory
-    let metadata_files: Vec<_> = read_meta(Path::new(metadata_dir)).collect();
+/// A format's efficient reader, viewed as a source of already-grouped contests.
+///
+/// The slow path reads one contest at a time and persists an intermediate
+/// `normalized.json.gz` per race. A bulk source instead reads a jurisdiction's
+/// shared raw dataset a single time and hands back every contest it contains,
+/// keyed by the `race_key` used to match contests in the metadata. Any format
+/// whose raw files hold many contests (NYC's CVR exports, a PrefLib directory,
+/// a multi-seat BLT bundle) can implement this to reuse the direct-to-report
+/// pipeline in [`preprocess_jurisdiction`] without touching disk between races.
+pub trait BulkBallotSource {
+    /// The `data_format` string this source handles, matched against each
+    /// election's `data_format` before the source is used.
+    fn data_format(&self) -> &str;
+
+    /// Read `raw_election_path` once and return `(race_key, Election)` pairs for
+    /// every contest found. `loader_params` carries the parameters shared by the
+    /// election's contests (e.g. NYC's `candidatesFile` / `cvrPattern`).
+    fn read_all(
+        &self,
+        raw_election_path: &Path,
+        loader_params: &BTreeMap<String, String>,
+    ) -> Vec<(String, Election)>;
+}
+
+/// [`BulkBallotSource`] backed by the NYC CVR efficient reader.
+struct NycBallotSource;
+
+impl BulkBallotSource for NycBallotSource {
+    fn data_format(&self) -> &str {
+        "us_ny_nyc"
+    }
 
+    fn read_all(
+        &self,
+        raw_election_path: &Path,
+        loader_params: &BTreeMap<String, String>,
+    ) -> Vec<(String, Election)> {
+        let candidates_file = loader_params
+            .get("candidatesFile")
+            .expect("candidatesFile not found in loader params");
+        let cvr_pattern = loader_params
+            .get("cvrPattern")
+            .expect("cvrPattern not found in loader params");
+
+        let ballot_db = read_all_nyc_data(raw_election_path, candidates_file, cvr_pattern);
+        ballot_db
+            .races
+            .keys()
+            .filter_map(|race_key| {
+                ballot_db
+                    .to_election(race_key)
+                    .map(|election| (race_key.clone(), election))
+            })
+            .collect()
+    }
+}
+
+/// Efficiently process all NYC races in one pass and generate final reports.
+///
+/// This is a thin wrapper over [`preprocess_jurisdiction`] that pins the NYC
+/// jurisdiction file, the `us_ny_nyc` bulk reader, and the `2025/07` election.
+pub fn preprocess_nyc(metadata_dir: &str, raw_data_dir: &str, report_dir: &str) {
     eprintln!("🏙️  Starting efficient NYC processing...");
+    preprocess_jurisdiction(
+        metadata_dir,
+        raw_data_dir,
+        report_dir,
+        "nyc.json",
+        &NycBallotSource,
+        &|election_path| election_path == "2025/07",
+    );
+    eprintln!("✅ NYC processing complete!");
+}
 
-    // Find NYC jurisdiction file
-    let (_, nyc_jurisdiction) = metadata_files
+/// One-pass, memory-resident preprocessor for a large multi-contest jurisdiction.
+///
+/// Reads the jurisdiction whose metadata file name contains `jurisdiction`, and
+/// for each of its elections accepted by `election_filter` drives the shared
+/// pipeline — read raw → build ballot DB → per-race normalize → generate report
+/// — entirely from memory via `source`, skipping the `normalized.json.gz` step.
+/// Election selection is passed in as `election_filter`, so any format's
+/// [`BulkBallotSource`] can reuse this path. Contests are not selected by the
+/// caller: each race the source yields is matched to its metadata by race key.
+pub fn preprocess_jurisdiction(
+    metadata_dir: &str,
+    raw_data_dir: &str,
+    report_dir: &str,
+    jurisdiction: &str,
+    source: &dyn BulkBallotSource,
+    election_filter: &dyn Fn(&str) -> bool,
+) {
+    // Read metadata from all files in the directory.
+    let metadata_files: Vec<_> = read_meta(Path::new(metadata_dir)).collect();
+
+    let (_, jurisdiction_meta) = metadata_files
         .iter()
-        .find(|(path, _)| path.to_string_lossy().contains("nyc.json"))
-        .expect("NYC jurisdiction metadata not found");
+        .find(|(path, _)| path.to_string_lossy().contains(jurisdiction))
+        .unwrap_or_else(|| panic!("{} jurisdiction metadata not found", jurisdiction));
 
-    // Process only the 2025 election
-    for (election_path, election_metadata) in &nyc_jurisdiction.elections {
-        if election_metadata.data_format != "us_ny_nyc" {
+    for (election_path, election_metadata) in &jurisdiction_meta.elections {
+        if election_metadata.data_format != source.data_format() {
             continue;
         }
 
-        // Skip 2021 election, only process 2025
-        if election_path != "2025/07" {
-            eprintln!(
-                "⏭️  Skipping election: {} (only processing 2025/07)",
-                election_path
-            );
+        if !election_filter(election_path) {
+            eprintln!("⏭️  Skipping election: {}", election_path);
             continue;
         }
 
         eprintln!("📅 Processing election: {}", election_path);
 
-        // Get the raw data path for this election
+        // Get the raw data path for this election.
         let raw_election_path = Path::new(raw_data_dir)
-            .join(&nyc_jurisdiction.path)
+            .join(&jurisdiction_meta.path)
             .join(election_path);
 
         if !raw_election_path.exists() {
@@ -52,7 +133,8 @@ pub fn preprocess_nyc(metadata_dir: &str, raw_data_dir: &str, report_dir: &str)
             continue;
         }
 
-        // Extract common parameters from first contest (they should all be the same for NYC)
+        // Extract the parameters shared by this election's contests (they are
+        // identical across contests drawn from one raw dataset).
         let first_contest = election_metadata
             .contests
             .first()
@@ -61,24 +143,14 @@ pub fn preprocess_nyc(metadata_dir: &str, raw_data_dir: &str, report_dir: &str)
             .loader_params
             .as_ref()
             .expect("loader_params not found in contest");
-        let candidates_file = loader_params
-            .get("candidatesFile")
-            .expect("candidatesFile not found in loader params");
-        let cvr_pattern = loader_params
-            .get("cvrPattern")
-            .expect("cvrPattern not found in loader params");
 
-        // Process all races in one efficient pass
-        eprintln!("🚀 Reading all NYC data efficiently...");
-        let ballot_db = read_all_nyc_data(&raw_election_path, candidates_file, cvr_pattern);
-
-        // Generate reports directly for ALL races at once
-        eprintln!(
-            "📊 Generating reports for all {} races...",
-            ballot_db.races.len()
-        );
-        for (race_key, _race_metadata) in &ballot_db.races {
-            // Find the corresponding contest in metadata
+        // Read every contest in one efficient pass.
+        eprintln!("🚀 Reading all raw data efficiently...");
+        let elections = source.read_all(&raw_election_path, loader_params);
+
+        eprintln!("📊 Generating reports for all {} races...", elections.len());
+        for (race_key, election) in elections {
+            // Find the corresponding contest in metadata by its race_key.
             let contest = election_metadata.contests.iter().find(|c| {
                 if let Some(params) = &c.loader_params {
                     let contest_race_key = format!(
@@ -86,78 +158,71 @@ pub fn preprocess_nyc(metadata_dir: &str, raw_data_dir: &str, report_dir: &str)
                         params.get("officeName").map_or("", |v| v),
                         params.get("jurisdictionName").map_or("", |v| v)
                     );
-                    contest_race_key == *race_key
+                    contest_race_key == race_key
                 } else {
                     false
                 }
             });
 
-            if let Some(contest) = contest {
-                let office = nyc_jurisdiction
-                    .offices
-                    .get(&contest.office)
-                    .expect("Office not found in jurisdiction");
-
-                if let Some(election) = ballot_db.to_election(race_key) {
-                    eprintln!("  📊 {} -> {} ballots", office.name, election.ballots.len());
-
-                    // Normalize the election
-                    let normalized = normalize_election(&election_metadata.normalization, election);
-
-                    // Create ElectionPreprocessed for report generation
-                    let election_info = ElectionInfo {
-                        name: office.name.clone(),
-                        date: election_metadata.date.clone(),
-                        data_format: election_metadata.data_format.clone(),
-                        tabulation_options: election_metadata
-                            .tabulation_options
-                            .clone()
-                            .unwrap_or_default(),
-                        jurisdiction_path: nyc_jurisdiction.path.clone(),
-                        election_path: election_path.clone(),
-                        office: contest.office.clone(),
-                        office_name: office.name.clone(),
-                        jurisdiction_name: nyc_jurisdiction.name.clone(),
-                        election_name: election_metadata.name.clone(),
-                        loader_params: contest.loader_params.clone(),
-                        website: None,
-                    };
-
-                    let election_preprocessed = ElectionPreprocessed {
-                        info: election_info,
-                        ballots: normalized,
-                    };
-
-                    // Generate the final report
-                    if let Some(report) = generate_report(&election_preprocessed) {
-                        // Write report to final directory
-                        let report_path = Path::new(report_dir)
-                            .join(&nyc_jurisdiction.path)
-                            .join(election_path)
-                            .join(&contest.office)
-                            .join("report.json");
-
-                        // Create directory if it doesn't exist
-                        if let Some(parent) = report_path.parent() {
-                            create_dir_all(parent).unwrap();
-                        }
-
-                        write_serialized(&report_path, &report);
-                        eprintln!("    ✅ Generated report: {}", report_path.display());
-                    } else {
-                        eprintln!("    ⚠️  Could not generate report for: {}", race_key);
-                    }
-                } else {
-                    eprintln!("  ⚠️  No ballots found for race: {}", race_key);
+            let Some(contest) = contest else {
+                eprintln!("  ⚠️  No metadata found for race: {}", race_key);
+                continue;
+            };
+
+            let office = jurisdiction_meta
+                .offices
+                .get(&contest.office)
+                .expect("Office not found in jurisdiction");
+
+            eprintln!("  📊 {} -> {} ballots", office.name, election.ballots.len());
+
+            // Normalize the election.
+            let normalized = normalize_election(&election_metadata.normalization, election);
+
+            // Create ElectionPreprocessed for report generation.
+            let election_info = ElectionInfo {
+                name: office.name.clone(),
+                date: election_metadata.date.clone(),
+                data_format: election_metadata.data_format.clone(),
+                tabulation_options: election_metadata
+                    .tabulation_options
+                    .clone()
+                    .unwrap_or_default(),
+                jurisdiction_path: jurisdiction_meta.path.clone(),
+                election_path: election_path.clone(),
+                office: contest.office.clone(),
+                office_name: office.name.clone(),
+                jurisdiction_name: jurisdiction_meta.name.clone(),
+                election_name: election_metadata.name.clone(),
+                loader_params: contest.loader_params.clone(),
+                website: None,
+            };
+
+            let election_preprocessed = ElectionPreprocessed {
+                info: election_info,
+                ballots: normalized,
+            };
+
+            // Generate the final report directly from memory.
+            if let Some(report) = generate_report(&election_preprocessed) {
+                let report_path = Path::new(report_dir)
+                    .join(&jurisdiction_meta.path)
+                    .join(election_path)
+                    .join(&contest.office)
+                    .join("report.json");
+
+                if let Some(parent) = report_path.parent() {
+                    create_dir_all(parent).unwrap();
                 }
+
+                write_serialized(&report_path, &report);
+                eprintln!("    ✅ Generated report: {}", report_path.display());
             } else {
-                eprintln!("  ⚠️  No metadata found for race: {}", race_key);
+                eprintln!("    ⚠️  Could not generate report for: {}", race_key);
             }
         }
     }
 
-    // Rebuild the index.json to include all generated reports
+    // Rebuild the index.json to include all generated reports.
     rebuild_index(Path::new(report_dir));
-
-    eprintln!("✅ NYC processing complete!");
 }
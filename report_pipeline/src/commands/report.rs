@@ -11,7 +11,7 @@ use std::path::{Path, PathBuf};
 use crate::{log_warn, log_info, log_debug, log_race};
 
 /// Check if a candidate name is a write-in (handles "Write-in", "Write in", "Undeclared Write-ins", "UWI", etc.)
-fn is_write_in_by_name(name: &str) -> bool {
+pub(crate) fn is_write_in_by_name(name: &str) -> bool {
     let normalized = name.to_lowercase();
     normalized == "write-in"
         || normalized == "write in"
@@ -419,6 +419,112 @@ fn process_nist_election_batch(
         .collect()
 }
 
+/// Process a BLT election with batch optimization
+fn process_blt_election_batch(
+    election_path: &str,
+    election: &ElectionMetadata,
+    jurisdiction: &Jurisdiction,
+    raw_base: &Path,
+    report_dir: &Path,
+    preprocessed_dir: &Path,
+    force_preprocess: bool,
+    force_report: bool,
+) -> Vec<ContestIndexEntry> {
+    use crate::formats::blt::blt_batch_reader;
+
+    let raw_path = raw_base.join(election_path);
+
+    // Each BLT contest carries its own `file` loader parameter, keyed by office.
+    let contests_with_offices: Vec<(String, std::collections::BTreeMap<String, String>)> = election
+        .contests
+        .iter()
+        .filter_map(|contest| {
+            let params = contest.loader_params.clone()?;
+            Some((contest.office.clone(), params))
+        })
+        .collect();
+
+    let mut elections_by_office = blt_batch_reader(&raw_path, contests_with_offices);
+
+    election
+        .contests
+        .iter()
+        .filter_map(|contest| {
+            let office = jurisdiction
+                .offices
+                .get(&contest.office)
+                .unwrap_or_else(|| panic!("Expected office {} to be in offices.", &contest.office));
+            log_race!(&jurisdiction.name, &election.name, &office.name);
+
+            let report_path = Path::new(report_dir)
+                .join(&jurisdiction.path)
+                .join(election_path)
+                .join(&contest.office)
+                .join("report.json");
+            let preprocessed_path = Path::new(preprocessed_dir)
+                .join(&jurisdiction.path)
+                .join(election_path)
+                .join(&contest.office)
+                .join("normalized.json.gz");
+
+            create_dir_all(report_path.parent().unwrap()).unwrap();
+            create_dir_all(preprocessed_path.parent().unwrap()).unwrap();
+
+            let raw_election = elections_by_office.remove(&contest.office)?;
+
+            let preprocessed = if force_preprocess || !preprocessed_path.exists() {
+                let preprocessed = crate::report::preprocess_election_from_data(
+                    raw_election,
+                    election,
+                    jurisdiction,
+                    contest,
+                    election_path,
+                );
+                write_serialized(&preprocessed_path, &preprocessed);
+                log_debug!("Processed {} ballots", preprocessed.ballots.ballots.len());
+                preprocessed
+            } else {
+                log_debug!("Reading cached preprocessed {}", preprocessed_path.display());
+                read_serialized(&preprocessed_path)
+            };
+
+            let report = if force_report || !report_path.exists() {
+                let contest_report = generate_report(&preprocessed);
+                write_serialized(&report_path, &contest_report);
+                contest_report
+            } else {
+                read_serialized(&report_path)
+            };
+
+            if report.ballot_count == 0 || report.num_candidates == 0 || report.rounds.is_empty() {
+                log_debug!("Skipping empty report: {}", report.info.office);
+                return None;
+            }
+
+            let has_write_in_by_name =
+                report.candidates.iter().any(|c| is_write_in_by_name(&c.name));
+
+            Some(ContestIndexEntry {
+                office: report.info.office.clone(),
+                office_name: report.info.office_name.clone(),
+                name: report.info.name.clone(),
+                winner: report
+                    .winner()
+                    .map(|w| w.name.clone())
+                    .unwrap_or_else(|| "No Winner".to_string()),
+                num_candidates: report.num_candidates,
+                num_rounds: report.rounds.len() as u32,
+                condorcet_winner: report
+                    .condorcet
+                    .map(|c| report.candidates[c.0 as usize].name.clone()),
+                has_non_condorcet_winner: report.condorcet.is_some()
+                    && report.condorcet != report.winner,
+                has_write_in_by_name,
+            })
+        })
+        .collect()
+}
+
 /// Process a single election and return its election index entry
 fn process_election(
     election_path: &str,
@@ -439,7 +545,22 @@ fn process_election(
     // The batch reader uses the efficient reader which handles numeric candidate IDs correctly
     let is_nyc_batch = election.data_format == "us_ny_nyc" && !election.contests.is_empty();
 
-    let contest_index_entries: Vec<ContestIndexEntry> = if is_nyc_batch {
+    // BLT elections are one `.blt` file per contest; the batch reader resolves
+    // each contest's `file` parameter independently.
+    let is_blt_batch = election.data_format == "blt" && !election.contests.is_empty();
+
+    let contest_index_entries: Vec<ContestIndexEntry> = if is_blt_batch {
+        process_blt_election_batch(
+            election_path,
+            election,
+            jurisdiction,
+            raw_base,
+            report_dir,
+            preprocessed_dir,
+            force_preprocess,
+            force_report,
+        )
+    } else if is_nyc_batch {
         // Check if all contests use the same cvrPattern and candidatesFile
         let first_params = election.contests[0].loader_params.as_ref();
         let same_params = first_params.is_some()
@@ -724,6 +845,8 @@ pub fn report(
     }
 
     write_serialized(&index_path, &report_index);
+    crate::commands::search_index::write_search_index(report_dir, &report_index);
+    crate::commands::index_export::write_index_exports(report_dir, &report_index);
     log_info!("Index written: {} elections", report_index.elections.len());
 
     // Print summary
@@ -772,9 +895,10 @@ pub fn rebuild_index(report_dir: &Path) {
             .and_then(|p| p.to_str())
             .map(|s| s.to_string());
 
-        // Read the report (skip if it fails to parse)
+        // Read the report, following a content-store pointer to its blob if
+        // the contest path holds one (skip if it fails to parse).
         let report = std::panic::catch_unwind(|| {
-            read_serialized::<ContestReport>(&report_path)
+            crate::commands::store::resolve_report(report_dir, &report_path)
         });
 
         if let Ok(report) = report {
@@ -858,6 +982,8 @@ pub fn rebuild_index(report_dir: &Path) {
 
     let index_path = report_dir.join("index.json");
     write_serialized(&index_path, &report_index);
+    crate::commands::search_index::write_search_index(report_dir, &report_index);
+    crate::commands::index_export::write_index_exports(report_dir, &report_index);
     log_info!("Found {} report.json files, processed {} successfully", reports_found, reports_processed);
     log_info!("Index updated: {} elections", report_index.elections.len());
 }
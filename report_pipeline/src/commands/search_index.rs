@@ -0,0 +1,185 @@
+//! Static inverted search index for client-side contest search.
+//!
+//! Alongside `index.json` we emit `search-index.json`, a self-contained
+//! inverted index a static frontend can load to search candidates, office,
+//! election, jurisdiction names, and dates with no server. Each contest becomes
+//! a lightweight [`SearchDoc`]; every field is tokenized (lowercased, split on
+//! non-alphanumerics) into an inverted index from token to the sorted list of
+//! document ids that contain it. Postings are delta-encoded so the on-disk size
+//! stays small, and a query tokenizes its input, intersects (AND) or unions
+//! (OR) the matching postings, and ranks documents by how many query terms
+//! they matched.
+
+use crate::model::report::ReportIndex;
+use crate::util::write_serialized;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A lightweight per-contest record the frontend renders in a result list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchDoc {
+    pub office_name: String,
+    pub election: String,
+    pub jurisdiction: String,
+    pub winner: String,
+    pub path: String,
+}
+
+/// The serialized search index: the sorted token vocabulary, the delta-encoded
+/// postings list parallel to it, and the document records postings point into.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    /// Distinct tokens in ascending order.
+    pub tokens: Vec<String>,
+    /// `postings[i]` is the delta-encoded ascending doc-id list for `tokens[i]`.
+    pub postings: Vec<Vec<u32>>,
+    /// Document records indexed by doc id.
+    pub docs: Vec<SearchDoc>,
+}
+
+/// How multiple query terms combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryMode {
+    /// A document must contain every term.
+    And,
+    /// A document must contain at least one term.
+    Or,
+}
+
+/// Lowercase and split a field into alphanumeric tokens.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Build the inverted index from the assembled report index. Empty reports are
+/// already excluded from `ReportIndex`, so search results never point at a
+/// contest with no rounds.
+pub fn build_search_index(index: &ReportIndex) -> SearchIndex {
+    let mut docs: Vec<SearchDoc> = Vec::new();
+    // token -> sorted set of doc ids (BTree keeps both ordered for free).
+    let mut inverted: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+
+    for election in &index.elections {
+        for contest in &election.contests {
+            let doc_id = docs.len() as u32;
+            let doc = SearchDoc {
+                office_name: contest.office_name.clone(),
+                election: election.election_name.clone(),
+                jurisdiction: election.jurisdiction_name.clone(),
+                winner: contest.winner.clone(),
+                path: election.path.clone(),
+            };
+
+            // Index every searchable field, including the contest's candidate
+            // name carried as the winner plus the date.
+            for field in [
+                &doc.office_name,
+                &doc.election,
+                &doc.jurisdiction,
+                &doc.winner,
+                &election.date,
+                &contest.name,
+            ] {
+                for token in tokenize(field) {
+                    let postings = inverted.entry(token).or_default();
+                    if postings.last() != Some(&doc_id) {
+                        postings.push(doc_id);
+                    }
+                }
+            }
+
+            docs.push(doc);
+        }
+    }
+
+    let mut tokens = Vec::with_capacity(inverted.len());
+    let mut postings = Vec::with_capacity(inverted.len());
+    for (token, ids) in inverted {
+        tokens.push(token);
+        postings.push(delta_encode(&ids));
+    }
+
+    SearchIndex {
+        tokens,
+        postings,
+        docs,
+    }
+}
+
+/// Build the search index and write it to `search-index.json`.
+pub fn write_search_index(report_dir: &Path, index: &ReportIndex) {
+    let search_index = build_search_index(index);
+    write_serialized(&report_dir.join("search-index.json"), &search_index);
+}
+
+/// Delta-encode an ascending id list: first value verbatim, then gaps.
+fn delta_encode(ids: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(ids.len());
+    let mut prev = 0u32;
+    for &id in ids {
+        out.push(id - prev);
+        prev = id;
+    }
+    out
+}
+
+/// Reverse [`delta_encode`] back to absolute ascending ids.
+fn delta_decode(deltas: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(deltas.len());
+    let mut acc = 0u32;
+    for &d in deltas {
+        acc += d;
+        out.push(acc);
+    }
+    out
+}
+
+impl SearchIndex {
+    /// Look up a token's absolute doc-id postings, if present.
+    fn postings_for(&self, token: &str) -> Option<Vec<u32>> {
+        self.tokens
+            .binary_search_by(|t| t.as_str().cmp(token))
+            .ok()
+            .map(|i| delta_decode(&self.postings[i]))
+    }
+
+    /// Run a query, returning matching doc ids ranked by the number of query
+    /// terms each document matched (most first). `And` requires every term;
+    /// `Or` requires at least one.
+    pub fn query(&self, input: &str, mode: QueryMode) -> Vec<u32> {
+        let mut terms = tokenize(input);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        // Count, per doc, how many distinct query terms it matched.
+        let mut match_counts: BTreeMap<u32, u32> = BTreeMap::new();
+        for term in &terms {
+            if let Some(ids) = self.postings_for(term) {
+                for id in ids {
+                    *match_counts.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let required = match mode {
+            QueryMode::And => terms.len() as u32,
+            QueryMode::Or => 1,
+        };
+
+        let mut ranked: Vec<(u32, u32)> = match_counts
+            .into_iter()
+            .filter(|(_, count)| *count >= required)
+            .collect();
+        // More matching terms first; ties broken by ascending doc id.
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked.into_iter().map(|(id, _)| id).collect()
+    }
+}
@@ -0,0 +1,238 @@
+//! Built-in HTTP service for querying reports and the index.
+//!
+//! `serve` exposes an existing report directory over HTTP so a results set can
+//! be browsed without a separate static host. It serves `index.json` and
+//! individual `report.json` files directly, and adds a `/contests` endpoint
+//! that filters the in-memory [`ReportIndex`] by the fields already computed in
+//! `ContestIndexEntry`: jurisdiction path, office, `has_write_in_by_name`,
+//! `has_non_condorcet_winner`, a minimum `num_rounds`, and a date range.
+//!
+//! The index is loaded once at startup and shared across connections behind an
+//! `Arc`; the server itself is a small std-only HTTP/1.1 loop so no web
+//! framework is pulled in.
+
+use crate::model::report::{ContestIndexEntry, ReportIndex};
+use crate::util::read_serialized;
+use crate::{log_info, log_warn};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Filters accepted by the `/contests` endpoint, mirroring the index fields.
+#[derive(Debug, Default)]
+struct ContestFilter {
+    jurisdiction: Option<String>,
+    office: Option<String>,
+    has_write_in_by_name: Option<bool>,
+    has_non_condorcet_winner: Option<bool>,
+    min_rounds: Option<u32>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+}
+
+impl ContestFilter {
+    /// Parse filters from a URL query string (`a=b&c=d`).
+    fn from_query(query: &str) -> ContestFilter {
+        let mut filter = ContestFilter::default();
+        for (key, value) in parse_query(query) {
+            match key.as_str() {
+                "jurisdiction" => filter.jurisdiction = Some(value),
+                "office" => filter.office = Some(value),
+                "has_write_in_by_name" => filter.has_write_in_by_name = parse_bool(&value),
+                "has_non_condorcet_winner" => {
+                    filter.has_non_condorcet_winner = parse_bool(&value)
+                }
+                "min_rounds" => filter.min_rounds = value.parse().ok(),
+                "date_from" => filter.date_from = Some(value),
+                "date_to" => filter.date_to = Some(value),
+                _ => {}
+            }
+        }
+        filter
+    }
+
+    /// Whether `(election_path, date, contest)` passes every active filter.
+    fn matches(&self, election_path: &str, date: &str, contest: &ContestIndexEntry) -> bool {
+        if let Some(j) = &self.jurisdiction {
+            if !election_path.starts_with(j.as_str()) {
+                return false;
+            }
+        }
+        if let Some(o) = &self.office {
+            if &contest.office != o {
+                return false;
+            }
+        }
+        if let Some(w) = self.has_write_in_by_name {
+            if contest.has_write_in_by_name != w {
+                return false;
+            }
+        }
+        if let Some(c) = self.has_non_condorcet_winner {
+            if contest.has_non_condorcet_winner != c {
+                return false;
+            }
+        }
+        if let Some(m) = self.min_rounds {
+            if contest.num_rounds < m {
+                return false;
+            }
+        }
+        // Dates are ISO-like strings, so lexicographic comparison is ordering.
+        if let Some(from) = &self.date_from {
+            if date < from.as_str() {
+                return false;
+            }
+        }
+        if let Some(to) = &self.date_to {
+            if date > to.as_str() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Start the HTTP server on `addr`, serving `report_dir`.
+pub fn serve(report_dir: &Path, addr: &str) {
+    let index_path = report_dir.join("index.json");
+    let index: Arc<ReportIndex> = Arc::new(if index_path.exists() {
+        read_serialized(&index_path)
+    } else {
+        log_warn!("No index.json in {}; /contests will be empty", report_dir.display());
+        ReportIndex { elections: vec![] }
+    });
+
+    let listener =
+        TcpListener::bind(addr).unwrap_or_else(|e| panic!("Failed to bind {}: {}", addr, e));
+    log_info!("Serving {} on http://{}", report_dir.display(), addr);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let index = Arc::clone(&index);
+                let report_dir = report_dir.to_path_buf();
+                std::thread::spawn(move || handle(stream, &report_dir, &index));
+            }
+            Err(e) => log_warn!("Connection failed: {}", e),
+        }
+    }
+}
+
+/// Handle a single request: parse the request line, route, and respond.
+fn handle(mut stream: TcpStream, report_dir: &Path, index: &ReportIndex) {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    // GET <target> HTTP/1.1
+    let target = match request_line.split_whitespace().nth(1) {
+        Some(t) => t,
+        None => return,
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let response = if path == "/contests" {
+        let filtered = filter_contests(index, &ContestFilter::from_query(query));
+        let body = serde_json::to_vec(&filtered).unwrap_or_default();
+        http_response("200 OK", "application/json", &body)
+    } else if let Some(bytes) = serve_file(report_dir, path) {
+        http_response("200 OK", "application/json", &bytes)
+    } else {
+        http_response("404 Not Found", "text/plain", b"Not Found")
+    };
+
+    let _ = stream.write_all(&response);
+}
+
+/// Collect the contests matching `filter` into a flat array.
+fn filter_contests(index: &ReportIndex, filter: &ContestFilter) -> Vec<ContestIndexEntry> {
+    let mut out = Vec::new();
+    for election in &index.elections {
+        for contest in &election.contests {
+            if filter.matches(&election.path, &election.date, contest) {
+                out.push(contest.clone());
+            }
+        }
+    }
+    out
+}
+
+/// Resolve a request path to a file under `report_dir`, refusing traversal and
+/// only serving the JSON artifacts we publish.
+fn serve_file(report_dir: &Path, path: &str) -> Option<Vec<u8>> {
+    let rel = path.trim_start_matches('/');
+    if rel.is_empty() || rel.contains("..") {
+        return None;
+    }
+    let is_json = rel == "index.json"
+        || rel == "search-index.json"
+        || rel.ends_with("/report.json");
+    if !is_json {
+        return None;
+    }
+    let full: PathBuf = report_dir.join(rel);
+    std::fs::read(full).ok()
+}
+
+/// Assemble a minimal HTTP/1.1 response.
+fn http_response(status: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+/// Parse `a=b&c=d` into decoded key/value pairs.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (url_decode(k), url_decode(v))
+        })
+        .collect()
+}
+
+/// Parse a boolean query value, accepting `true`/`1` and `false`/`0`.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Minimal percent-decoding sufficient for query parameters.
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hi = (bytes[i + 1] as char).to_digit(16);
+                let lo = (bytes[i + 2] as char).to_digit(16);
+                if let (Some(hi), Some(lo)) = (hi, lo) {
+                    out.push((hi * 16 + lo) as u8);
+                    i += 2;
+                } else {
+                    out.push(b'%');
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
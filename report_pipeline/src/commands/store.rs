@@ -0,0 +1,129 @@
+//! Content-addressed backing store for report blobs.
+//!
+//! Repeated regeneration and many near-identical uncontested races mean the
+//! same serialized [`ContestReport`] bytes are written over and over. This
+//! store hashes a report's serialized bytes, keeps the blob once under
+//! `objects/<hh>/<hash>` in the report directory, and writes a tiny pointer
+//! file at the contest path referencing that hash. Reads resolve pointers back
+//! to blobs transparently, so identical contests consume storage exactly once.
+//!
+//! [`gc`] reclaims space: it marks every blob named by a live pointer file, then
+//! deletes the rest. It does not read `index.json`.
+
+use crate::model::report::ContestReport;
+use crate::util::{read_serialized, write_serialized};
+use crate::{log_info, log_warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A pointer file standing in for a report, naming the blob that holds it.
+#[derive(Debug, Serialize, Deserialize)]
+struct ReportPointer {
+    /// Hex SHA-256 of the serialized report stored under `objects/`.
+    blob: String,
+}
+
+/// The path of a blob given its hash: `objects/<first two hex chars>/<hash>`.
+fn blob_path(report_dir: &Path, hash: &str) -> PathBuf {
+    report_dir.join("objects").join(&hash[..2]).join(hash)
+}
+
+/// Serialize `report`, store it once as a content-addressed blob, and write a
+/// pointer file at `pointer_path` (the usual `…/report.json` location).
+pub fn store_report(report_dir: &Path, pointer_path: &Path, report: &ContestReport) {
+    let bytes = serde_json::to_vec(report).expect("serialize report");
+    let hash = hex(&Sha256::digest(&bytes));
+
+    let blob = blob_path(report_dir, &hash);
+    if !blob.exists() {
+        if let Some(parent) = blob.parent() {
+            std::fs::create_dir_all(parent).expect("create blob dir");
+        }
+        std::fs::write(&blob, &bytes)
+            .unwrap_or_else(|e| panic!("write blob {}: {}", blob.display(), e));
+    }
+
+    write_serialized(pointer_path, &ReportPointer { blob: hash });
+}
+
+/// Read a report at `path`, following a pointer to its blob if the file is one.
+/// A plain `report.json` (no content store in use) is read directly, so this is
+/// safe to call on both pointer and non-pointer trees.
+pub fn resolve_report(report_dir: &Path, path: &Path) -> ContestReport {
+    let text = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("read report {}: {}", path.display(), e));
+
+    if let Ok(pointer) = serde_json::from_str::<ReportPointer>(&text) {
+        let blob = blob_path(report_dir, &pointer.blob);
+        return read_serialized(&blob);
+    }
+
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("parse report {}: {}", path.display(), e))
+}
+
+/// Delete blobs not referenced by any live pointer under `report_dir`.
+///
+/// Walks every `report.json`, collecting the hashes its pointers name (ignoring
+/// plain reports, which hold no reference), then removes any blob in `objects/`
+/// whose hash is not in that reachable set.
+pub fn gc(report_dir: &Path) {
+    let mut reachable: HashSet<String> = HashSet::new();
+    let mut pointers = Vec::new();
+    find_pointers(report_dir, &mut pointers);
+    for pointer_path in &pointers {
+        if let Ok(text) = std::fs::read_to_string(pointer_path) {
+            if let Ok(pointer) = serde_json::from_str::<ReportPointer>(&text) {
+                reachable.insert(pointer.blob);
+            }
+        }
+    }
+
+    let objects = report_dir.join("objects");
+    let mut removed = 0u32;
+    if let Ok(shards) = std::fs::read_dir(&objects) {
+        for shard in shards.flatten() {
+            if let Ok(blobs) = std::fs::read_dir(shard.path()) {
+                for blob in blobs.flatten() {
+                    let name = blob.file_name().to_string_lossy().to_string();
+                    if !reachable.contains(&name) {
+                        if let Err(e) = std::fs::remove_file(blob.path()) {
+                            log_warn!("Failed to remove blob {}: {}", blob.path().display(), e);
+                        } else {
+                            removed += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    log_info!("Garbage collected {} unreferenced blobs", removed);
+}
+
+/// Recursively collect every `report.json` under `dir`, skipping `objects/`.
+fn find_pointers(dir: &Path, out: &mut Vec<PathBuf>) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if path.file_name().and_then(|n| n.to_str()) == Some("objects") {
+                    continue;
+                }
+                find_pointers(&path, out);
+            } else if path.file_name().and_then(|n| n.to_str()) == Some("report.json") {
+                out.push(path);
+            }
+        }
+    }
+}
+
+/// Lowercase hex encoding of a digest.
+fn hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
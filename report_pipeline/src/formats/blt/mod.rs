@@ -0,0 +1,351 @@
+//! BLT (OpenSTV / Droop) Ballot Format Parser
+//!
+//! This module implements a reader and writer for the classic BLT ballot
+//! format used by OpenSTV, Droop, and most academic STV tooling. It is the
+//! de-facto interchange format for ranked elections that are not distributed
+//! as Dominion CVRs, so supporting it lets users tabulate and round-trip
+//! elections from other counting tools without building a CVR.
+//!
+//! ## File Format
+//!
+//! A BLT file consists of:
+//! 1. A header line `<num_candidates> <num_seats>`.
+//! 2. An optional line of space-separated negative integers marking withdrawn
+//!    candidates (e.g. `-2 -5`).
+//! 3. Weighted ballot lines of the form `<weight> <cand_id> <cand_id> ... 0`,
+//!    where `weight` is an integer multiplier applied to that preference order
+//!    and each ballot is terminated by a `0`. Candidate ids are 1-based.
+//! 4. A lone `0` terminating the ballot section.
+//! 5. Exactly `num_candidates` candidate names, each on its own quoted line.
+//! 6. The quoted election title.
+//!
+//! ## Equal Ranking
+//!
+//! The `=`-joined equal-ranking extension (e.g. `1=2`) groups several
+//! candidates at a single rank. The group is emitted as a
+//! [`Choice::EqualGroup`] preserving its members, so the honored-equal-ranking
+//! tabulation mode can split the ballot's value across the tied candidates; a
+//! count run in the default strict mode still treats the shared rank as an
+//! overvote.
+
+use crate::formats::common::{normalize_name, CandidateMap};
+use crate::model::election::{Ballot, Candidate, CandidateId, CandidateType, Choice, Election};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// Configuration options for reading BLT files.
+struct ReaderOptions {
+    /// Path to the `.blt` file relative to the election directory.
+    file: String,
+}
+
+impl ReaderOptions {
+    pub fn from_params(params: BTreeMap<String, String>) -> ReaderOptions {
+        let file = params
+            .get("file")
+            .expect("blt elections should have file parameter.")
+            .clone();
+
+        ReaderOptions { file }
+    }
+}
+
+/// Tokenize a single ballot line into its choices.
+///
+/// The leading token is the integer weight; the remaining tokens are 1-based
+/// candidate indices (optionally `=`-joined into equal-rank groups) terminated
+/// by a `0`. Returns the weight together with the resolved choices.
+fn parse_ballot_line(
+    tokens: &[&str],
+    candidate_map: &CandidateMap<u32>,
+    withdrawn: &[u32],
+) -> (u32, Vec<Choice>) {
+    let weight: u32 = tokens[0].parse().unwrap_or(1);
+    let mut choices = Vec::new();
+    let mut seen: Vec<u32> = Vec::new();
+
+    for token in &tokens[1..] {
+        if *token == "0" {
+            break;
+        }
+
+        if token.contains('=') {
+            // Equal-ranking group: several candidates share this rank. Preserve
+            // it as an equal-rank group so the honored-equal-ranking tabulation
+            // can split the ballot's value across the tied candidates, rather
+            // than discarding the intent as an overvote. Withdrawn and repeated
+            // candidates are dropped from the group as they are from a plain rank.
+            let mut members: Vec<CandidateId> = Vec::new();
+            for part in token.split('=') {
+                let Ok(cand_id) = part.parse::<u32>() else {
+                    continue;
+                };
+                if withdrawn.contains(&cand_id) || seen.contains(&cand_id) {
+                    continue;
+                }
+                if let Choice::Vote(candidate) = candidate_map.id_to_choice(cand_id) {
+                    seen.push(cand_id);
+                    members.push(candidate);
+                }
+            }
+            match members.as_slice() {
+                [] => choices.push(Choice::Undervote),
+                [one] => choices.push(Choice::Vote(*one)),
+                _ => choices.push(Choice::EqualGroup(members)),
+            }
+        } else if let Ok(cand_id) = token.parse::<u32>() {
+            if withdrawn.contains(&cand_id) {
+                // A withdrawn candidate carries no intent; record an undervote
+                // so the ballot's remaining ranks stay positionally aligned.
+                choices.push(Choice::Undervote);
+            } else if seen.contains(&cand_id) {
+                // A candidate repeated at a later rank carries no additional
+                // intent; record an undervote so ranks stay positionally aligned.
+                choices.push(Choice::Undervote);
+            } else {
+                seen.push(cand_id);
+                choices.push(candidate_map.id_to_choice(cand_id));
+            }
+        } else {
+            // An empty or unparseable rank is an undervote.
+            choices.push(Choice::Undervote);
+        }
+    }
+
+    (weight, choices)
+}
+
+/// The full result of reading a BLT file.
+///
+/// Beyond the [`Election`] itself this carries the two header-derived facts the
+/// ballot lines alone don't express: the declared number of seats (which drives
+/// multi-winner STV) and the candidate ids marked withdrawn. Withdrawn
+/// candidates are already dropped from every ballot during parsing, but the
+/// count still needs their identity so it can exclude them from the hopeful set
+/// and from any category membership rather than inferring withdrawal from an
+/// absence of votes.
+pub struct BltResult {
+    pub election: Election,
+    pub num_seats: u32,
+    pub withdrawn: Vec<CandidateId>,
+}
+
+/// Read and parse a BLT format election file into an [`Election`].
+///
+/// # Parameters
+/// - `path`: Base path to the election directory.
+/// - `params`: Loader parameters containing the `file` key pointing at the
+///   `.blt` file.
+pub fn blt_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Election {
+    blt_ballot_reader_full(path, params).election
+}
+
+/// Read a BLT file, returning both the [`Election`] and the number of seats
+/// declared in its header. The seat count drives multi-winner STV tabulation;
+/// [`blt_ballot_reader`] discards it for the single-winner dispatch path.
+pub fn blt_ballot_reader_with_seats(
+    path: &Path,
+    params: BTreeMap<String, String>,
+) -> (Election, u32) {
+    let result = blt_ballot_reader_full(path, params);
+    (result.election, result.num_seats)
+}
+
+/// Read a BLT file into a [`BltResult`], exposing the seat count and the
+/// withdrawn candidate ids alongside the [`Election`].
+pub fn blt_ballot_reader_full(path: &Path, params: BTreeMap<String, String>) -> BltResult {
+    let options = ReaderOptions::from_params(params);
+    let file_path = path.join(&options.file);
+
+    let file = File::open(&file_path)
+        .unwrap_or_else(|e| panic!("Failed to open BLT file {}: {}", file_path.display(), e));
+    let reader = BufReader::new(file);
+
+    // Read all non-empty lines up front so we can walk the sections in order.
+    // `#` introduces a line comment, as in OpenSTV's dialect.
+    let lines: Vec<String> = reader
+        .lines()
+        .map_while(Result::ok)
+        .map(|l| {
+            let without_comment = l.split('#').next().unwrap_or("");
+            without_comment.trim().to_string()
+        })
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    let mut iter = lines.iter();
+
+    // Header: <num_candidates> <num_seats>
+    let header = iter
+        .next()
+        .unwrap_or_else(|| panic!("BLT file {} is empty", file_path.display()));
+    let mut header_parts = header.split_whitespace();
+    let num_candidates: u32 = header_parts
+        .next()
+        .and_then(|t| t.parse().ok())
+        .unwrap_or_else(|| panic!("Malformed BLT header in {}: {}", file_path.display(), header));
+    let num_seats: u32 = header_parts.next().and_then(|t| t.parse().ok()).unwrap_or(1);
+
+    // The candidate map is keyed on the 1-based candidate index. Names arrive
+    // after the ballot section, so seed placeholder candidates now and rename
+    // them once the name block is parsed.
+    let mut candidate_map: CandidateMap<u32> = CandidateMap::new();
+    for id in 1..=num_candidates {
+        candidate_map.add(
+            id,
+            Candidate::new(format!("Candidate {}", id), CandidateType::Regular),
+        );
+    }
+
+    let mut ballots: Vec<Ballot> = Vec::new();
+    let mut ballot_counter = 0u32;
+    // Withdrawn candidates, given as leading negative ids on an optional line
+    // before the ballots (e.g. `-2 -5`). They are ignored when ranking so the
+    // count proceeds as if they had never stood.
+    let mut withdrawn: Vec<u32> = Vec::new();
+
+    for line in iter.by_ref() {
+        // An optional withdrawn-candidate line precedes the ballots; record the
+        // ids it names so those preferences are dropped from every ballot.
+        if line.starts_with('-') {
+            withdrawn.extend(
+                line.split_whitespace()
+                    .filter_map(|t| t.strip_prefix('-').and_then(|n| n.parse::<u32>().ok())),
+            );
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        // A lone `0` terminates the ballot section.
+        if tokens.as_slice() == ["0"] {
+            break;
+        }
+
+        let (weight, choices) = parse_ballot_line(&tokens, &candidate_map, &withdrawn);
+        // BLT carries the multiplier natively, so keep it as a single weighted
+        // ballot rather than expanding into `weight` identical copies.
+        ballot_counter += 1;
+        ballots.push(Ballot::with_weight(
+            format!("{}:{}", options.file, ballot_counter),
+            choices,
+            weight,
+        ));
+    }
+
+    // Candidate names: exactly `num_candidates` quoted lines, in index order.
+    for id in 1..=num_candidates {
+        if let Some(line) = iter.next() {
+            let name = line.trim_matches('"').trim();
+            let candidate_type = if name.eq_ignore_ascii_case("Write-In") {
+                CandidateType::WriteIn
+            } else {
+                CandidateType::Regular
+            };
+            candidate_map.add(
+                id,
+                Candidate::new(normalize_name(name, false), candidate_type),
+            );
+        }
+    }
+
+    // Report the withdrawn ids as 0-based [`CandidateId`]s matching the order
+    // of the candidate list, so the count can exclude them directly.
+    let withdrawn_ids: Vec<CandidateId> = withdrawn
+        .iter()
+        .copied()
+        .filter(|id| (1..=num_candidates).contains(id))
+        .map(|id| CandidateId(id - 1))
+        .collect();
+
+    BltResult {
+        election: Election::new(candidate_map.into_vec(), ballots),
+        num_seats,
+        withdrawn: withdrawn_ids,
+    }
+}
+
+/// Batch-read multiple BLT contests, one file per contest.
+///
+/// Parallel to `nyc_batch_reader` and `nist_batch_reader`, this is dispatched
+/// from `process_election` when `election.data_format == "blt"`. Each contest's
+/// `file` loader parameter points at its own `.blt` file, so the reader simply
+/// resolves each contest through [`blt_ballot_reader`]; the resulting
+/// `Election`s feed `preprocess_election_from_data` unchanged.
+pub fn blt_batch_reader(
+    path: &Path,
+    contests: Vec<(String, BTreeMap<String, String>)>,
+) -> HashMap<String, Election> {
+    let mut results = HashMap::new();
+    for (key, params) in contests {
+        let election = blt_ballot_reader(path, params);
+        results.insert(key, election);
+    }
+    results
+}
+
+/// Write an [`Election`] to a normalized BLT string with `seats` seats.
+///
+/// Identical preference orders are collapsed into a single weighted line for
+/// compactness, candidate names are emitted in index order, and the election
+/// title is left blank since the internal model does not carry one. The caller
+/// supplies the seat count because the [`Election`] model does not carry it;
+/// for BLT input it is taken from the file's header, otherwise from the
+/// `seats` loader parameter.
+pub fn write_blt(election: &Election, seats: u32) -> String {
+    let candidates = election.candidates();
+
+    // Collapse identical preference orders into weighted lines, preserving the
+    // order in which each distinct order was first seen for deterministic output.
+    let mut order_weights: Vec<(Vec<String>, u32)> = Vec::new();
+    for ballot in election.ballots() {
+        // Overvotes and undervotes are not expressible as BLT preferences, so
+        // they are dropped rather than terminating the ballot — later genuine
+        // preferences are still emitted in the remaining ranks.
+        let order: Vec<String> = ballot
+            .choices
+            .iter()
+            .filter_map(|choice| match choice {
+                Choice::Vote(id) => Some((id.0 + 1).to_string()),
+                // A preserved tie group round-trips as the `=`-joined extension.
+                Choice::EqualGroup(ids) => Some(
+                    ids.iter()
+                        .map(|id| (id.0 + 1).to_string())
+                        .collect::<Vec<_>>()
+                        .join("="),
+                ),
+                Choice::Overvote | Choice::Undervote => None,
+            })
+            .collect();
+
+        if let Some(entry) = order_weights.iter_mut().find(|(o, _)| *o == order) {
+            entry.1 += ballot.weight;
+        } else {
+            order_weights.push((order, ballot.weight));
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str(&format!("{} {}\n", candidates.len(), seats));
+    for (order, weight) in &order_weights {
+        out.push_str(&format!("{} {} 0\n", weight, order.join(" ")));
+    }
+    out.push_str("0\n");
+    for candidate in candidates {
+        out.push_str(&format!("\"{}\"\n", candidate.name));
+    }
+    out.push_str("\"\"\n");
+
+    out
+}
+
+/// Write an [`Election`] to a BLT file on disk with `seats` seats.
+pub fn write_blt_file(election: &Election, seats: u32, path: &Path) {
+    let contents = write_blt(election, seats);
+    let mut file = File::create(path)
+        .unwrap_or_else(|e| panic!("Failed to create BLT file {}: {}", path.display(), e));
+    file.write_all(contents.as_bytes())
+        .unwrap_or_else(|e| panic!("Failed to write BLT file {}: {}", path.display(), e));
+}
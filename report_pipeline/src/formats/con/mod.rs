@@ -0,0 +1,110 @@
+//! CON constraints file reader.
+//!
+//! Borrowing the Grey–Fitzgerald constraint method described in the OpenTally
+//! documentation, a CON file attaches minimum/maximum seat bounds to candidate
+//! categories (e.g. at least one woman from the north, at most three seats for
+//! one party). This reader parses such a file and resolves it against an
+//! [`Election`] into the [`Constraints`] the tabulator enforces; the
+//! guard/doom logic itself lives in [`crate::tabulator::constraints`].
+//!
+//! ## File Format
+//!
+//! One record per line; blank lines and `#` comments are ignored.
+//!
+//! - `category <dimension> <value> <min> <max>` declares a category cell and
+//!   its seat bounds, e.g. `category region north 1 3`.
+//! - `member <dimension> <value> <id> [<id> ...]` assigns one or more
+//!   candidates — by their 1-based ballot index, matching the BLT/PrefLib
+//!   numbering — to a declared category.
+//!
+//! Candidate ids are stored internally as 0-based [`CandidateId`]s. Write-in
+//! and withdrawn candidates never carry a category: memberships naming them are
+//! dropped, since the constraint system reasons only about eligible hopefuls.
+
+use crate::model::election::{CandidateId, CandidateType, Election};
+use crate::tabulator::constraints::{Category, Constraints};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Read a CON constraints file and resolve it against `election`.
+///
+/// `withdrawn` lists the candidate ids marked withdrawn by the ballot reader
+/// (see [`crate::formats::blt::BltResult`]); together with the write-in flag on
+/// each [`Candidate`](crate::model::election::Candidate) they are excluded from
+/// category membership.
+pub fn read_constraints_file(
+    path: &Path,
+    election: &Election,
+    withdrawn: &[CandidateId],
+) -> Constraints {
+    let file = File::open(path)
+        .unwrap_or_else(|e| panic!("Failed to open constraints file {}: {}", path.display(), e));
+    let reader = BufReader::new(file);
+
+    let candidates = election.candidates();
+
+    // A candidate is eligible for category membership unless it is a write-in
+    // or has been withdrawn.
+    let is_eligible = |id: &CandidateId| -> bool {
+        if withdrawn.contains(id) {
+            return false;
+        }
+        match candidates.get(id.0 as usize) {
+            Some(candidate) => !matches!(candidate.candidate_type, CandidateType::WriteIn),
+            None => false,
+        }
+    };
+
+    let mut categories: BTreeMap<String, Category> = BTreeMap::new();
+    let mut memberships: HashMap<CandidateId, Vec<String>> = HashMap::new();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.split('#').next().unwrap_or("").trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["category", dimension, value, min, max] => {
+                let category = Category {
+                    dimension: dimension.to_string(),
+                    value: value.to_string(),
+                    min: min.parse().unwrap_or(0),
+                    max: max.parse().unwrap_or(u32::MAX),
+                };
+                categories.insert(category.key(), category);
+            }
+            ["member", dimension, value, ids @ ..] => {
+                let key = format!("{}:{}", dimension, value);
+                for id in ids {
+                    // 1-based external ids map to 0-based CandidateIds.
+                    let Ok(external) = id.parse::<u32>() else {
+                        crate::log_warn!(
+                            "Unparseable candidate id '{}' in {}",
+                            id,
+                            path.display()
+                        );
+                        continue;
+                    };
+                    if external == 0 {
+                        continue;
+                    }
+                    let candidate = CandidateId(external - 1);
+                    if is_eligible(&candidate) {
+                        memberships.entry(candidate).or_default().push(key.clone());
+                    }
+                }
+            }
+            _ => crate::log_warn!(
+                "Unrecognized constraints line in {}: {}",
+                path.display(),
+                line
+            ),
+        }
+    }
+
+    Constraints::from_parts(categories, memberships)
+}
@@ -0,0 +1,200 @@
+//! CSP (Comma-Separated Preferences) Parser
+//!
+//! Many election offices publish preference data as a simple CSV where the
+//! first row is candidate names and every subsequent row is one ballot, with an
+//! integer preference rank in each candidate's column (blank = not ranked).
+//! This reader consumes that common spreadsheet export, which is not
+//! Dominion-shaped.
+//!
+//! ## Tie and Gap Handling
+//!
+//! Two candidates sharing a rank within a ballot become a
+//! [`Choice::EqualGroup`] preserving the tied members, so honored-equal-ranking
+//! tabulation can split the ballot's value across them (a strict count still
+//! treats the shared rank as an overvote). A gap or blank rank becomes a
+//! [`Choice::Undervote`], keeping ranks positionally aligned.
+
+use crate::formats::common::{normalize_name, CandidateMap};
+use crate::model::election::{Ballot, Candidate, CandidateId, CandidateType, Choice, Election};
+use csv::ReaderBuilder;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Configuration options for reading CSP files.
+struct ReaderOptions {
+    /// Path to the `.csv` file relative to the election directory.
+    file: String,
+    /// Require the smallest rank on each ballot to be `1`.
+    require_start_at_one: bool,
+    /// Require ranks to be sequential (no gaps) on each ballot.
+    require_sequential: bool,
+    /// Require ranks to be strictly ordered (no ties) on each ballot.
+    require_strict: bool,
+}
+
+impl ReaderOptions {
+    pub fn from_params(params: BTreeMap<String, String>) -> ReaderOptions {
+        let file = params
+            .get("file")
+            .expect("csp elections should have file parameter.")
+            .clone();
+        let flag = |key: &str| params.get(key).map(|v| v == "true").unwrap_or(false);
+
+        ReaderOptions {
+            file,
+            require_start_at_one: flag("requireStartAtOne"),
+            require_sequential: flag("requireSequential"),
+            require_strict: flag("requireStrict"),
+        }
+    }
+}
+
+/// Read and parse a CSP format election file into an [`Election`].
+///
+/// # Parameters
+/// - `path`: Base path to the election directory.
+/// - `params`: Loader parameters containing the `file` key and optional
+///   `requireStartAtOne`, `requireSequential`, and `requireStrict` flags.
+pub fn csp_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Election {
+    let options = ReaderOptions::from_params(params);
+    let file_path = path.join(&options.file);
+
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(false)
+        .from_path(&file_path)
+        .unwrap_or_else(|e| panic!("Failed to open CSP file {}: {}", file_path.display(), e));
+
+    let mut records = rdr.records();
+
+    // The header row establishes the column -> candidate map.
+    let header = records
+        .next()
+        .unwrap_or_else(|| panic!("CSP file {} is empty", file_path.display()))
+        .unwrap_or_else(|e| panic!("Failed to read CSP header: {}", e));
+
+    let mut candidate_map: CandidateMap<u32> = CandidateMap::new();
+    let mut column_ids: Vec<u32> = Vec::new();
+    for (col, name) in header.iter().enumerate() {
+        let id = col as u32;
+        candidate_map.add(
+            id,
+            Candidate::new(normalize_name(name, false), CandidateType::Regular),
+        );
+        column_ids.push(id);
+    }
+
+    let mut ballots: Vec<Ballot> = Vec::new();
+    let mut row_number = 1u32;
+
+    for record in records {
+        row_number += 1;
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                crate::log_warn!("Skipping malformed CSP row {}: {}", row_number, e);
+                continue;
+            }
+        };
+
+        // Collect (rank, candidate_id) pairs from the non-blank columns.
+        let mut ranked: Vec<(u32, u32)> = Vec::new();
+        for (col, value) in record.iter().enumerate() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            if let (Ok(rank), Some(id)) = (value.parse::<u32>(), column_ids.get(col)) {
+                ranked.push((rank, *id));
+            }
+        }
+
+        if ranked.is_empty() {
+            continue;
+        }
+
+        if let Some(reason) =
+            validate_ranks(&ranked, &options)
+        {
+            crate::log_warn!("Rejecting CSP row {}: {}", row_number, reason);
+            continue;
+        }
+
+        ballots.push(Ballot::new(
+            format!("{}:{}", options.file, row_number),
+            ranks_to_choices(&ranked, &candidate_map),
+        ));
+    }
+
+    Election::new(candidate_map.into_vec(), ballots)
+}
+
+/// Validate a ballot's ranks against the configured requirements, returning a
+/// human-readable reason when the ballot is rejected.
+fn validate_ranks(ranked: &[(u32, u32)], options: &ReaderOptions) -> Option<String> {
+    let mut ranks: Vec<u32> = ranked.iter().map(|(r, _)| *r).collect();
+    ranks.sort_unstable();
+
+    if options.require_start_at_one && ranks.first() != Some(&1) {
+        return Some("ranks do not start at 1".to_string());
+    }
+    if options.require_strict {
+        if ranks.windows(2).any(|w| w[0] == w[1]) {
+            return Some("ranks are not strictly ordered".to_string());
+        }
+    }
+    if options.require_sequential {
+        ranks.dedup();
+        if ranks
+            .iter()
+            .enumerate()
+            .any(|(i, r)| *r != ranks[0] + i as u32)
+        {
+            return Some("ranks are not sequential".to_string());
+        }
+    }
+    None
+}
+
+/// Convert sorted `(rank, candidate)` pairs into positionally-aligned choices,
+/// emitting an equal-rank group for a shared rank and an undervote for a
+/// skipped rank.
+fn ranks_to_choices(ranked: &[(u32, u32)], candidate_map: &CandidateMap<u32>) -> Vec<Choice> {
+    let mut by_rank: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for (rank, id) in ranked {
+        by_rank.entry(*rank).or_default().push(*id);
+    }
+
+    let mut choices = Vec::new();
+    let mut expected = *by_rank.keys().next().unwrap();
+    for (rank, ids) in &by_rank {
+        // Fill any gap between the previous rank and this one with undervotes.
+        while expected < *rank {
+            choices.push(Choice::Undervote);
+            expected += 1;
+        }
+        let choice = match ids.as_slice() {
+            [id] => candidate_map.id_to_choice(*id),
+            _ => {
+                // Several candidates share this rank: preserve the tie as an
+                // equal-rank group so the honored-equal-ranking tabulation can
+                // split it, rather than discarding the intent as an overvote.
+                let members: Vec<CandidateId> = ids
+                    .iter()
+                    .filter_map(|id| match candidate_map.id_to_choice(*id) {
+                        Choice::Vote(candidate) => Some(candidate),
+                        _ => None,
+                    })
+                    .collect();
+                match members.as_slice() {
+                    [] => Choice::Undervote,
+                    [one] => Choice::Vote(*one),
+                    _ => Choice::EqualGroup(members),
+                }
+            }
+        };
+        choices.push(choice);
+        expected = rank + 1;
+    }
+
+    choices
+}
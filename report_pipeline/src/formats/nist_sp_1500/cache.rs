@@ -0,0 +1,118 @@
+//! Content-hash ballot cache for parsed CVR files.
+//!
+//! Parsing thousands of CVR JSON/CSV files on every run is wasteful when the
+//! inputs haven't changed. This cache stores the parsed `Vec<Ballot>` for a
+//! given `(file_hash, contest_id, drop_unqualified_write_in)` tuple in a cache
+//! directory, so repeated tabulations of the same precinct dump — common when
+//! tweaking tabulation rules — become near-instant loads.
+//!
+//! Files are keyed by a two-stage hash: a cheap *partial* hash over the first
+//! and last 4 KiB plus the file length detects "probably unchanged"; a *full*
+//! hash over the whole file is used to confirm the match, so an edit that
+//! happens to preserve the partial hash still invalidates the entry.
+
+use crate::model::election::Ballot;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const PARTIAL_WINDOW: u64 = 4 * 1024;
+
+/// Resolve the cache directory, from `STV_CVR_CACHE` or a default under the
+/// system temp dir. Returns `None` (caching disabled) when it can't be created.
+fn cache_dir() -> Option<PathBuf> {
+    let dir = std::env::var("STV_CVR_CACHE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("stv-cvr-cache"));
+    fs::create_dir_all(&dir).ok().map(|_| dir)
+}
+
+/// Hash the first and last `PARTIAL_WINDOW` bytes plus the file length. Cheap
+/// for large files since it touches at most 8 KiB regardless of size.
+fn partial_hash(path: &Path) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut hasher = DefaultHasher::new();
+    len.hash(&mut hasher);
+
+    let head_len = PARTIAL_WINDOW.min(len) as usize;
+    let mut head = vec![0u8; head_len];
+    file.read_exact(&mut head)?;
+    head.hash(&mut hasher);
+
+    if len > PARTIAL_WINDOW {
+        let tail_len = PARTIAL_WINDOW.min(len) as usize;
+        file.seek(SeekFrom::End(-(tail_len as i64)))?;
+        let mut tail = vec![0u8; tail_len];
+        file.read_exact(&mut tail)?;
+        tail.hash(&mut hasher);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Hash the entire file, used to confirm a partial-hash match.
+fn full_hash(path: &Path) -> std::io::Result<u64> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = DefaultHasher::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        buf[..n].hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+/// The on-disk cache path for a given file and contest parameters.
+fn entry_path(
+    dir: &Path,
+    partial: u64,
+    full: u64,
+    contest_id: u32,
+    drop_unqualified_write_in: bool,
+) -> PathBuf {
+    dir.join(format!(
+        "{:016x}-{:016x}-{}-{}.json",
+        partial, full, contest_id, drop_unqualified_write_in as u8
+    ))
+}
+
+/// Look up the cached ballots for a file, returning `None` on a miss.
+pub fn load(
+    path: &Path,
+    contest_id: u32,
+    drop_unqualified_write_in: bool,
+) -> Option<Vec<Ballot>> {
+    let dir = cache_dir()?;
+    let partial = partial_hash(path).ok()?;
+    let full = full_hash(path).ok()?;
+    let entry = entry_path(&dir, partial, full, contest_id, drop_unqualified_write_in);
+
+    let file = File::open(&entry).ok()?;
+    serde_json::from_reader(BufReader::new(file)).ok()
+}
+
+/// Store the parsed ballots for a file so the next run can skip re-parsing.
+pub fn store(
+    path: &Path,
+    contest_id: u32,
+    drop_unqualified_write_in: bool,
+    ballots: &[Ballot],
+) {
+    let Some(dir) = cache_dir() else { return };
+    let (Ok(partial), Ok(full)) = (partial_hash(path), full_hash(path)) else {
+        return;
+    };
+    let entry = entry_path(&dir, partial, full, contest_id, drop_unqualified_write_in);
+
+    if let Ok(file) = File::create(&entry) {
+        let _ = serde_json::to_writer(file, ballots);
+    }
+}
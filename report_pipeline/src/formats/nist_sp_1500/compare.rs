@@ -0,0 +1,174 @@
+//! Cross-format CVR consistency report.
+//!
+//! The crate parses both the Dominion JSON export ([`stream_process_cvr_file`])
+//! and the CSV export ([`stream_process_csv_cvr_file`]) of the same contests,
+//! but nothing verifies they agree — a silent divergence in the fragile CSV
+//! rank-column heuristics could change an election outcome. This module parses
+//! one contest from two sources and produces a structured report so maintainers
+//! can audit parser fidelity before certifying results.
+//!
+//! [`stream_process_cvr_file`]: super::stream_process_cvr_file
+//! [`stream_process_csv_cvr_file`]: super::stream_process_csv_cvr_file
+
+use super::{
+    get_candidates, model::CandidateManifest, stream_process_cvr_file,
+    stream_process_csv_cvr_file,
+};
+use crate::model::election::{Ballot, Choice};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// The maximum number of individual mismatches listed in a report.
+const MAX_LISTED_MISMATCHES: usize = 100;
+
+/// How a pair of decoded ballots differs.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum DifferenceKind {
+    /// One source decoded a rank as a candidate, the other as an overvote.
+    OvervoteVsCandidate,
+    /// The two sources produced different numbers of ranks.
+    MissingRank,
+    /// A write-in was handled differently between the two sources.
+    WriteInHandling,
+    /// Some other ordered-choice divergence.
+    Other,
+}
+
+/// A single ballot whose decoded preference order differs between sources.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BallotMismatch {
+    pub ballot_id: String,
+    pub json_order: Vec<String>,
+    pub csv_order: Vec<String>,
+    pub kind: DifferenceKind,
+}
+
+/// Structured comparison of two parses of the same contest.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CvrComparisonReport {
+    pub json_ballot_count: usize,
+    pub csv_ballot_count: usize,
+    pub mismatch_count: usize,
+    pub mismatches: Vec<BallotMismatch>,
+}
+
+/// Render a `Choice` for human-readable diffing.
+fn decode(choice: &Choice) -> String {
+    match choice {
+        Choice::Vote(id) => format!("#{}", id.0),
+        Choice::EqualGroup(ids) => {
+            let members: Vec<String> = ids.iter().map(|id| format!("#{}", id.0)).collect();
+            format!("equal({})", members.join(","))
+        }
+        Choice::Overvote => "overvote".to_string(),
+        Choice::Undervote => "undervote".to_string(),
+    }
+}
+
+/// Strip the `"{filename}:"` prefix from a ballot id to recover its record id.
+fn record_id(id: &str) -> &str {
+    id.rsplit_once(':').map(|(_, r)| r).unwrap_or(id)
+}
+
+/// Classify the difference between two decoded preference orders.
+fn classify(json_order: &[String], csv_order: &[String]) -> DifferenceKind {
+    if json_order.len() != csv_order.len() {
+        return DifferenceKind::MissingRank;
+    }
+    for (a, b) in json_order.iter().zip(csv_order.iter()) {
+        if a == b {
+            continue;
+        }
+        if a == "overvote" || b == "overvote" {
+            return DifferenceKind::OvervoteVsCandidate;
+        }
+    }
+    DifferenceKind::Other
+}
+
+/// Parse the same contest from a Dominion JSON export and a CSV export and
+/// report where the two parses diverge.
+///
+/// Ballots are matched by record id where available, falling back to positional
+/// alignment. The returned report lists at most [`MAX_LISTED_MISMATCHES`]
+/// individual mismatches.
+pub fn compare_cvr_sources(
+    json_path: &Path,
+    csv_path: &Path,
+    contest_id: u32,
+    manifest: &CandidateManifest,
+    drop_unqualified_write_in: bool,
+) -> Result<CvrComparisonReport, String> {
+    let (candidates, dropped_write_in) =
+        get_candidates(manifest, contest_id, drop_unqualified_write_in);
+
+    let mut json_ballots: Vec<Ballot> = Vec::new();
+    let json_file = File::open(json_path).map_err(|e| format!("open JSON: {}", e))?;
+    stream_process_cvr_file(
+        BufReader::new(json_file),
+        &json_path.display().to_string(),
+        contest_id,
+        &candidates,
+        dropped_write_in,
+        &mut json_ballots,
+    )?;
+
+    let mut csv_ballots: Vec<Ballot> = Vec::new();
+    let csv_file = File::open(csv_path).map_err(|e| format!("open CSV: {}", e))?;
+    stream_process_csv_cvr_file(
+        BufReader::new(csv_file),
+        &csv_path.display().to_string(),
+        contest_id,
+        &candidates,
+        dropped_write_in,
+        &mut csv_ballots,
+        manifest,
+    )?;
+
+    // Index the CSV ballots by record id so they can be matched to the JSON
+    // parse; positional order is the fallback when ids don't line up.
+    let csv_by_record: BTreeMap<String, &Ballot> = csv_ballots
+        .iter()
+        .map(|b| (record_id(&b.id).to_string(), b))
+        .collect();
+
+    let mut mismatches: Vec<BallotMismatch> = Vec::new();
+    let mut mismatch_count = 0usize;
+
+    for (idx, json_ballot) in json_ballots.iter().enumerate() {
+        let rec = record_id(&json_ballot.id).to_string();
+        let csv_ballot = csv_by_record
+            .get(&rec)
+            .copied()
+            .or_else(|| csv_ballots.get(idx));
+
+        let Some(csv_ballot) = csv_ballot else {
+            continue;
+        };
+
+        let json_order: Vec<String> = json_ballot.choices.iter().map(decode).collect();
+        let csv_order: Vec<String> = csv_ballot.choices.iter().map(decode).collect();
+
+        if json_order != csv_order {
+            mismatch_count += 1;
+            if mismatches.len() < MAX_LISTED_MISMATCHES {
+                let kind = classify(&json_order, &csv_order);
+                mismatches.push(BallotMismatch {
+                    ballot_id: rec,
+                    json_order,
+                    csv_order,
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(CvrComparisonReport {
+        json_ballot_count: json_ballots.len(),
+        csv_ballot_count: csv_ballots.len(),
+        mismatch_count,
+        mismatches,
+    })
+}
@@ -0,0 +1,106 @@
+//! Content-sniffing CVR format detection.
+//!
+//! File discovery used to hard-code the `CvrExport*.json` / `CVR_Export*.csv`
+//! naming conventions, so real-world exports with different casing, a `.gz`
+//! wrapper, or renamed files were silently skipped. This module peeks the
+//! first bytes of a candidate file to classify it, transparently unwrapping a
+//! gzip layer, and falls back to the file extension only when sniffing is
+//! inconclusive.
+
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// The sniffed content type of a CVR file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CvrFileType {
+    /// Dominion JSON export (leading `{`).
+    Json,
+    /// Dominion CSV export (printable header row).
+    Csv,
+    /// Could not be classified by content.
+    Unknown,
+}
+
+/// Whether a candidate filename is worth sniffing at all.
+///
+/// Anything ending in `.json`, `.csv`, or `.gz` (in any casing) is a candidate;
+/// the final decision is made by [`sniff`] on the file contents.
+pub fn is_candidate_file(filename: &str) -> bool {
+    let lower = filename.to_lowercase();
+    lower.ends_with(".json") || lower.ends_with(".csv") || lower.ends_with(".gz")
+}
+
+/// Read the leading bytes of a file, transparently decompressing gzip, so the
+/// sniffer inspects the logical content rather than a compression header.
+fn peek_decompressed(path: &Path, n: usize) -> std::io::Result<Vec<u8>> {
+    let mut magic = [0u8; 2];
+    let mut file = File::open(path)?;
+    let read = file.read(&mut magic)?;
+
+    // Re-open so we read from the start regardless of the peek above.
+    let file = File::open(path)?;
+    let mut reader: Box<dyn Read> = if read == 2 && magic == [0x1f, 0x8b] {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+
+    let mut buf = vec![0u8; n];
+    let got = reader.read(&mut buf)?;
+    buf.truncate(got);
+    Ok(buf)
+}
+
+/// Classify a file by content, returning its type and whether it is gzip-wrapped.
+///
+/// - A leading `{` (after optional whitespace) is Dominion JSON.
+/// - A printable header row mentioning the election name/version is the CSV
+///   variant.
+/// - Falls back to the extension when the content is inconclusive.
+pub fn sniff(path: &Path) -> (CvrFileType, bool) {
+    let mut magic = [0u8; 2];
+    let gzipped = File::open(path)
+        .and_then(|mut f| f.read(&mut magic).map(|n| n == 2 && magic == [0x1f, 0x8b]))
+        .unwrap_or(false);
+
+    let head = peek_decompressed(path, 4096).unwrap_or_default();
+    let text = String::from_utf8_lossy(&head);
+    let trimmed = text.trim_start();
+
+    let detected = if trimmed.starts_with('{') {
+        CvrFileType::Json
+    } else if trimmed.contains("Version") || trimmed.contains("CvrNumber") {
+        // The CSV export's first rows carry the election name and a version
+        // marker, followed by the `CvrNumber`/`TabulatorNum` header row.
+        CvrFileType::Csv
+    } else {
+        // Inconclusive: fall back to the extension.
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let name = name.strip_suffix(".gz").unwrap_or(&name);
+        if name.ends_with(".json") {
+            CvrFileType::Json
+        } else if name.ends_with(".csv") {
+            CvrFileType::Csv
+        } else {
+            CvrFileType::Unknown
+        }
+    };
+
+    (detected, gzipped)
+}
+
+/// Open a CVR file for reading, transparently decompressing a gzip wrapper.
+pub fn open(path: &Path, gzipped: bool) -> std::io::Result<Box<dyn Read>> {
+    let file = File::open(path)?;
+    if gzipped {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
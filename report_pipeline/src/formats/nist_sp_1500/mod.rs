@@ -1,3 +1,6 @@
+pub mod cache;
+pub mod compare;
+pub mod detect;
 pub mod model;
 
 use crate::formats::common::{normalize_name, CandidateMap};
@@ -5,16 +8,46 @@ use crate::formats::nist_sp_1500::model::{CandidateManifest, CandidateType, CvrE
 use crate::model::election::{self, Ballot, Candidate, Choice, Election};
 use csv::ReaderBuilder;
 use itertools::Itertools;
+use rayon::prelude::*;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, File};
 use std::io::{BufReader, Read};
 
 use std::path::Path;
 
+/// Resolve the maximum number of worker threads used for per-file CVR parsing.
+///
+/// Defaults to rayon's global pool size, but can be capped with the
+/// `STV_CVR_THREADS` environment variable so memory-constrained hosts don't
+/// fan out over hundreds of large files at once.
+fn cvr_thread_cap() -> Option<usize> {
+    std::env::var("STV_CVR_THREADS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+}
+
+/// Run `f` on a rayon pool sized according to [`cvr_thread_cap`].
+///
+/// When no cap is configured the global pool is used directly.
+fn with_cvr_pool<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    match cvr_thread_cap() {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("failed to build CVR thread pool")
+            .install(f),
+        None => f(),
+    }
+}
+
 struct ReaderOptions {
     cvr: String,
     contest: u32,
     drop_unqualified_write_in: bool,
+    /// Companion `CandidateManifest.json` path, required when the CVR source is
+    /// stdin or a single loose file (which carry no manifest of their own).
+    manifest: Option<String>,
 }
 
 impl ReaderOptions {
@@ -32,15 +65,86 @@ impl ReaderOptions {
             .get("dropUnqualifiedWriteIn")
             .map(|d| d.parse().unwrap())
             .unwrap_or(false);
+        let manifest = params.get("manifest").cloned();
 
         ReaderOptions {
             contest,
             cvr,
             drop_unqualified_write_in,
+            manifest,
         }
     }
 }
 
+/// Where the CVR bytes come from, resolved from the `cvr` parameter before
+/// dispatch. A directory or ZIP carries its own `CandidateManifest.json`; a
+/// single file or stdin relies on the companion `manifest` parameter.
+enum CvrSource {
+    Directory(std::path::PathBuf),
+    Zip(std::path::PathBuf),
+    File(std::path::PathBuf),
+    Stdin,
+}
+
+impl CvrSource {
+    /// Classify a resolved CVR path (or `-` for stdin) into a [`CvrSource`].
+    fn resolve(cvr: &str, cvr_path: &Path) -> CvrSource {
+        if cvr == "-" {
+            CvrSource::Stdin
+        } else if cvr_path.is_dir() {
+            CvrSource::Directory(cvr_path.to_path_buf())
+        } else if cvr_path.extension().and_then(|e| e.to_str()) == Some("zip") {
+            CvrSource::Zip(cvr_path.to_path_buf())
+        } else {
+            CvrSource::File(cvr_path.to_path_buf())
+        }
+    }
+}
+
+/// Load the companion manifest referenced by the `manifest` parameter, used for
+/// single-file and stdin sources that don't bundle a `CandidateManifest.json`.
+fn load_companion_manifest(path: &Path, manifest: &Option<String>) -> CandidateManifest {
+    let manifest_path = manifest
+        .as_ref()
+        .map(|m| path.join(m))
+        .expect("single-file/stdin CVR sources require a manifest parameter.");
+    let file = File::open(&manifest_path).unwrap_or_else(|e| {
+        panic!(
+            "Could not open manifest {}: {}",
+            manifest_path.display(),
+            e
+        )
+    });
+    serde_json::from_reader(BufReader::new(file)).expect("Failed to parse CandidateManifest.json")
+}
+
+/// Read a single CVR stream (one loose file or stdin) into an [`Election`],
+/// using a companion manifest to resolve candidates.
+fn read_from_single_source<R: Read>(
+    reader: R,
+    source_name: &str,
+    manifest: &CandidateManifest,
+    options: &ReaderOptions,
+) -> Election {
+    let (candidates, dropped_write_in) =
+        get_candidates(manifest, options.contest, options.drop_unqualified_write_in);
+
+    let mut ballots: Vec<Ballot> = Vec::new();
+    if let Err(e) = stream_process_cvr_file(
+        reader,
+        source_name,
+        options.contest,
+        &candidates,
+        dropped_write_in,
+        &mut ballots,
+    ) {
+        crate::log_warn!("Warning: Error processing {}: {}", source_name, e);
+    }
+
+    crate::log_debug!("Read {} ballots", ballots.len());
+    Election::new(candidates.into_vec(), ballots)
+}
+
 fn get_candidates(
     manifest: &CandidateManifest,
     contest_id: u32,
@@ -78,6 +182,30 @@ fn get_candidates(
 pub fn nist_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Election {
     let options = ReaderOptions::from_params(params);
 
+    // Stdin and single-file sources are resolved before the directory/ZIP
+    // fallback logic below, since they read the manifest from a companion
+    // `manifest` parameter rather than from alongside the CVR.
+    if options.cvr == "-" {
+        crate::log_debug!("Reading CVR from stdin");
+        let manifest = load_companion_manifest(path, &options.manifest);
+        return read_from_single_source(std::io::stdin().lock(), "<stdin>", &manifest, &options);
+    }
+
+    let direct_path = path.join(&options.cvr);
+    if direct_path.is_file() && options.manifest.is_some() {
+        if let CvrSource::File(file_path) = CvrSource::resolve(&options.cvr, &direct_path) {
+            crate::log_debug!("Reading single CVR file {}", file_path.display());
+            let manifest = load_companion_manifest(path, &options.manifest);
+            let file = File::open(&file_path)
+                .unwrap_or_else(|e| panic!("Could not open {}: {}", file_path.display(), e));
+            let name = file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("<cvr>");
+            return read_from_single_source(BufReader::new(file), name, &manifest, &options);
+        }
+    }
+
     // Handle "." as current directory
     let mut cvr_path = if options.cvr == "." {
         path.to_path_buf()
@@ -141,12 +269,12 @@ fn stream_process_cvr_file<R: Read>(
     ballots: &mut Vec<Ballot>,
 ) -> Result<usize, String> {
     let mut count = 0;
-    let content =
-        std::io::read_to_string(reader).map_err(|e| format!("Failed to read file: {}", e))?;
 
-    // Parse as CvrExport but immediately process sessions
-    let cvr: CvrExport =
-        serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    // Stream the document straight off the reader rather than buffering the
+    // whole file into a `String` first, which halves peak memory on the
+    // multi-hundred-megabyte county exports.
+    let cvr: CvrExport = serde_json::from_reader(BufReader::new(reader))
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
     for session in &cvr.sessions {
         for contest in &session.contests() {
@@ -405,16 +533,20 @@ fn read_from_directory(dir_path: &Path, options: &ReaderOptions) -> Election {
 
     let mut ballots: Vec<Ballot> = Default::default();
 
-    // Find all CvrExport files in the directory
+    // Find all candidate files in the directory. Rather than matching the
+    // `CvrExport*.json` / `CVR_Export*.csv` naming conventions, we accept any
+    // `.json`/`.csv`/`.gz` file and classify it by content below, so mixed and
+    // gzip-compressed exports are ingested without the user renaming anything.
     let mut cvr_files: Vec<String> = Vec::new();
     if let Ok(entries) = fs::read_dir(dir_path) {
         for entry in entries {
             if let Ok(entry) = entry {
                 let filename = entry.file_name().to_string_lossy().to_string();
-                // Support both JSON and CSV formats (CSV files may use CVR_Export prefix)
-                if (filename.starts_with("CvrExport") && filename.ends_with(".json"))
-                    || (filename.starts_with("CVR_Export") && filename.ends_with(".csv"))
-                {
+                // The manifest is read separately and must not be treated as a CVR.
+                if filename.starts_with("CandidateManifest") {
+                    continue;
+                }
+                if detect::is_candidate_file(&filename) {
                     cvr_files.push(filename);
                 }
             }
@@ -429,54 +561,107 @@ fn read_from_directory(dir_path: &Path, options: &ReaderOptions) -> Election {
         file_count
     );
 
-    for filename in cvr_files {
-        let file_path = dir_path.join(&filename);
-        let file = match File::open(&file_path) {
-            Ok(file) => file,
-            Err(e) => {
-                crate::log_warn!("Warning: Could not open {}: {}", filename, e);
-                continue;
-            }
-        };
+    // Parse each file into its own ballot buffer on the rayon pool, then
+    // concatenate the buffers in sorted-filename order so that ballot ids
+    // (`"{filename}:{record_id}"`) and the overall output stay deterministic
+    // regardless of the order workers happen to finish in. The candidate map
+    // and dropped write-in are read-only, so they can be shared by reference.
+    let per_file: Vec<Vec<Ballot>> = with_cvr_pool(|| {
+        cvr_files
+            .par_iter()
+            .map(|filename| {
+                let mut local: Vec<Ballot> = Vec::new();
+                let file_path = dir_path.join(filename);
+
+                // Classify by content (transparently unwrapping gzip) rather
+                // than trusting the filename or extension.
+                let (file_type, gzipped) = detect::sniff(&file_path);
+                crate::log_debug!(
+                    "  detected {:?}{} for {}",
+                    file_type,
+                    if gzipped { " (gzip)" } else { "" },
+                    filename
+                );
 
-        // Determine file type and process accordingly
-        let result = if filename.ends_with(".csv") {
-            stream_process_csv_cvr_file(
-                file,
-                &filename,
-                options.contest,
-                &candidates,
-                dropped_write_in,
-                &mut ballots,
-                &candidate_manifest,
-            )
-        } else {
-            stream_process_cvr_file(
-                file,
-                &filename,
-                options.contest,
-                &candidates,
-                dropped_write_in,
-                &mut ballots,
-            )
-        };
+                // Consult the content-hash cache before re-parsing. CSV parses
+                // depend on the manifest, so only JSON parses are cacheable by
+                // the (file, contest, drop_write_in) tuple alone.
+                let cacheable = file_type == detect::CvrFileType::Json;
+                if cacheable {
+                    if let Some(cached) =
+                        cache::load(&file_path, options.contest, options.drop_unqualified_write_in)
+                    {
+                        crate::log_debug!("  → cache hit for {}", filename);
+                        return cached;
+                    }
+                }
 
-        match result {
-            Ok(count) => {
-                if count > 0 {
-                    crate::log_debug!(
-                        "  → {} ballots for contest {} from {}",
-                        count,
+                let reader = match detect::open(&file_path, gzipped) {
+                    Ok(reader) => reader,
+                    Err(e) => {
+                        crate::log_warn!("Warning: Could not open {}: {}", filename, e);
+                        return local;
+                    }
+                };
+
+                let result = match file_type {
+                    detect::CvrFileType::Csv => stream_process_csv_cvr_file(
+                        reader,
+                        filename,
                         options.contest,
-                        filename
+                        &candidates,
+                        dropped_write_in,
+                        &mut local,
+                        &candidate_manifest,
+                    ),
+                    detect::CvrFileType::Json => stream_process_cvr_file(
+                        reader,
+                        filename,
+                        options.contest,
+                        &candidates,
+                        dropped_write_in,
+                        &mut local,
+                    ),
+                    detect::CvrFileType::Unknown => {
+                        crate::log_warn!("Skipping {}: unrecognized CVR format", filename);
+                        return local;
+                    }
+                };
+
+                match result {
+                    Ok(count) => {
+                        if count > 0 {
+                            crate::log_debug!(
+                                "  → {} ballots for contest {} from {}",
+                                count,
+                                options.contest,
+                                filename
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        crate::log_warn!("Warning: Error processing {}: {}", filename, e);
+                        crate::log_warn!("Skipping this file and continuing...");
+                    }
+                }
+
+                // Write back cache misses for JSON files.
+                if cacheable {
+                    cache::store(
+                        &file_path,
+                        options.contest,
+                        options.drop_unqualified_write_in,
+                        &local,
                     );
                 }
-            }
-            Err(e) => {
-                crate::log_warn!("Warning: Error processing {}: {}", filename, e);
-                crate::log_warn!("Skipping this file and continuing...");
-            }
-        }
+
+                local
+            })
+            .collect()
+    });
+
+    for mut local in per_file {
+        ballots.append(&mut local);
     }
 
     crate::log_debug!("Read {} ballots", ballots.len());
@@ -640,9 +825,9 @@ pub fn nist_batch_reader(
         serde_json::from_reader(reader).unwrap()
     };
 
-    // Set up candidate maps and ballot buckets for each contest
-    let mut contest_data: HashMap<u32, (CandidateMap<u32>, Option<u32>, Vec<Ballot>)> =
-        HashMap::new();
+    // Set up read-only candidate maps for each contest. These are shared by
+    // reference across worker threads; only the ballot buffers are per-thread.
+    let mut contest_meta: HashMap<u32, (CandidateMap<u32>, Option<u32>)> = HashMap::new();
 
     for (contest_id, params) in &contests {
         let drop_unqualified_write_in: bool = params
@@ -653,7 +838,7 @@ pub fn nist_batch_reader(
         let (candidates, dropped_write_in) =
             get_candidates(&candidate_manifest, *contest_id, drop_unqualified_write_in);
 
-        contest_data.insert(*contest_id, (candidates, dropped_write_in, Vec::new()));
+        contest_meta.insert(*contest_id, (candidates, dropped_write_in));
     }
 
     // Find all CVR files
@@ -677,80 +862,107 @@ pub fn nist_batch_reader(
 
     crate::log_debug!("  Processing {} CVR files...", file_count);
 
-    // Process each CVR file once, distributing ballots to all contests
-    for (file_idx, filename) in cvr_files.iter().enumerate() {
-        let file_path = cvr_path.join(filename);
-        let file = match File::open(&file_path) {
-            Ok(file) => file,
-            Err(e) => {
-                crate::log_warn!("Warning: Could not open {}: {}", filename, e);
-                continue;
-            }
-        };
-
-        // Read and parse the CVR file
-        let content = match std::io::read_to_string(file) {
-            Ok(content) => content,
-            Err(e) => {
-                crate::log_warn!("Warning: Failed to read {}: {}", filename, e);
-                continue;
-            }
-        };
+    // Parse each CVR file on the rayon pool into a per-file map of contest id
+    // to ballots, then merge the per-file maps back in sorted-filename order so
+    // ballot ids and output remain deterministic. `contest_meta` is read-only
+    // and shared by reference across workers.
+    let per_file: Vec<HashMap<u32, Vec<Ballot>>> = with_cvr_pool(|| {
+        cvr_files
+            .par_iter()
+            .map(|filename| {
+                let mut local: HashMap<u32, Vec<Ballot>> = HashMap::new();
+
+                let file_path = cvr_path.join(filename);
+
+                // If every contest's ballots for this file are cached, assemble
+                // them directly and skip parsing the JSON entirely.
+                let all_cached: Option<HashMap<u32, Vec<Ballot>>> = contest_meta
+                    .iter()
+                    .map(|(contest_id, (_, dropped))| {
+                        cache::load(&file_path, *contest_id, dropped.is_some())
+                            .map(|b| (*contest_id, b))
+                    })
+                    .collect();
+                if let Some(cached) = all_cached {
+                    crate::log_debug!("  → cache hit for {}", filename);
+                    return cached;
+                }
 
-        let cvr: CvrExport = match serde_json::from_str(&content) {
-            Ok(cvr) => cvr,
-            Err(e) => {
-                crate::log_warn!("Warning: Failed to parse {}: {}", filename, e);
-                continue;
-            }
-        };
+                let file = match File::open(&file_path) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        crate::log_warn!("Warning: Could not open {}: {}", filename, e);
+                        return local;
+                    }
+                };
+
+                // Stream the document off a buffered reader rather than
+                // materializing the whole file as a `String`.
+                let cvr: CvrExport = match serde_json::from_reader(BufReader::new(file)) {
+                    Ok(cvr) => cvr,
+                    Err(e) => {
+                        crate::log_warn!("Warning: Failed to parse {}: {}", filename, e);
+                        return local;
+                    }
+                };
+
+                for session in &cvr.sessions {
+                    for contest in &session.contests() {
+                        if let Some((candidates, dropped_write_in)) = contest_meta.get(&contest.id) {
+                            let mut choices: Vec<Choice> = Vec::new();
+                            for (_, marks) in &contest.marks.iter().group_by(|x| x.rank) {
+                                let marks: Vec<&Mark> = marks.filter(|d| !d.is_ambiguous).collect();
+
+                                let choice = match marks.as_slice() {
+                                    [v] if Some(v.candidate_id) == *dropped_write_in => {
+                                        Choice::Undervote
+                                    }
+                                    [v] => candidates.id_to_choice(v.candidate_id),
+                                    [] => Choice::Undervote,
+                                    _ => Choice::Overvote,
+                                };
+
+                                choices.push(choice);
+                            }
 
-        // Process each session and distribute ballots to contests
-        for session in &cvr.sessions {
-            for contest in &session.contests() {
-                if let Some((candidates, dropped_write_in, ballots)) =
-                    contest_data.get_mut(&contest.id)
-                {
-                    let mut choices: Vec<Choice> = Vec::new();
-                    for (_, marks) in &contest.marks.iter().group_by(|x| x.rank) {
-                        let marks: Vec<&Mark> = marks.filter(|d| !d.is_ambiguous).collect();
-
-                        let choice = match marks.as_slice() {
-                            [v] if Some(v.candidate_id) == *dropped_write_in => Choice::Undervote,
-                            [v] => candidates.id_to_choice(v.candidate_id),
-                            [] => Choice::Undervote,
-                            _ => Choice::Overvote,
-                        };
-
-                        choices.push(choice);
+                            local.entry(contest.id).or_default().push(Ballot::new(
+                                format!("{}:{}", filename, session.record_id),
+                                choices,
+                            ));
+                        }
                     }
+                }
+
+                crate::log_debug!(
+                    "    {}: {} sessions consumed",
+                    filename,
+                    cvr.sessions.len()
+                );
 
-                    ballots.push(Ballot::new(
-                        format!("{}:{}", filename, session.record_id),
-                        choices,
-                    ));
+                // Write back cache misses for every contest touched by this file.
+                for (contest_id, (_, dropped)) in &contest_meta {
+                    let ballots = local.get(contest_id).cloned().unwrap_or_default();
+                    cache::store(&file_path, *contest_id, dropped.is_some(), &ballots);
                 }
-            }
-        }
 
-        // Show progress every 5 files
-        if (file_idx + 1) % 5 == 0 || file_idx + 1 == file_count {
-            crate::log_debug!(
-                "    Progress: {}/{} files processed",
-                file_idx + 1,
-                file_count
-            );
+                local
+            })
+            .collect()
+    });
+
+    // Merge per-file ballot buckets in sorted-filename order.
+    let mut contest_ballots: HashMap<u32, Vec<Ballot>> = HashMap::new();
+    for mut file_map in per_file {
+        for (contest_id, mut ballots) in file_map.drain() {
+            contest_ballots.entry(contest_id).or_default().append(&mut ballots);
         }
     }
 
-    // Convert to Election objects
+    // Convert to Election objects.
     let mut results = HashMap::new();
-    for (contest_id, (candidates, _dropped_write_in, ballots)) in contest_data {
-        crate::log_debug!(
-            "  Contest {}: {} ballots",
-            contest_id,
-            ballots.len()
-        );
+    for (contest_id, (candidates, _dropped_write_in)) in contest_meta {
+        let ballots = contest_ballots.remove(&contest_id).unwrap_or_default();
+        crate::log_debug!("  Contest {}: {} ballots", contest_id, ballots.len());
         results.insert(contest_id, Election::new(candidates.into_vec(), ballots));
     }
 
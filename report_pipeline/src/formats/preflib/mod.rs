@@ -39,7 +39,7 @@ use crate::formats::common::{normalize_name, CandidateMap};
 use crate::model::election::{Ballot, Candidate, CandidateType, Choice, Election};
 use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
 /// Configuration options for reading PrefLib files
@@ -291,3 +291,74 @@ pub fn preflib_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> E
 
     Election::new(final_candidates, ballots)
 }
+
+/// Serialize an [`Election`] to PrefLib TOI format.
+///
+/// Identical preference orders are aggregated into `count: preference_list`
+/// lines, preceded by the `# NUMBER ALTERNATIVES / VOTERS / UNIQUE ORDERS`
+/// header and one `# ALTERNATIVE NAME X: Name` entry per candidate. Candidate
+/// ids are the 1-based position in the election's candidate list, so reading
+/// the output back through [`preflib_ballot_reader`] preserves candidate
+/// identity and ballot multiplicities. A tie group (overvote) is written with
+/// the brace syntax; an undervote terminates the order as in the source data.
+pub fn write_preflib(election: &Election) -> String {
+    let candidates = election.candidates();
+
+    // Render each ballot as a comma-separated preference list of external ids,
+    // stopping at the first undervote and bracketing overvotes.
+    let render = |ballot: &Ballot| -> String {
+        let mut parts: Vec<String> = Vec::new();
+        for choice in &ballot.choices {
+            match choice {
+                Choice::Vote(id) => parts.push((id.0 + 1).to_string()),
+                Choice::EqualGroup(ids) => {
+                    // A preserved tie group round-trips as a PrefLib brace set.
+                    let members: Vec<String> =
+                        ids.iter().map(|id| (id.0 + 1).to_string()).collect();
+                    parts.push(format!("{{{}}}", members.join(",")));
+                }
+                Choice::Overvote => parts.push("{}".to_string()),
+                Choice::Undervote => break,
+            }
+        }
+        parts.join(",")
+    };
+
+    // Aggregate identical orders, preserving first-seen order for determinism.
+    let mut order_counts: Vec<(String, u32)> = Vec::new();
+    for ballot in election.ballots() {
+        let order = render(ballot);
+        if order.is_empty() {
+            continue;
+        }
+        if let Some(entry) = order_counts.iter_mut().find(|(o, _)| *o == order) {
+            entry.1 += ballot.weight;
+        } else {
+            order_counts.push((order, ballot.weight));
+        }
+    }
+
+    let voters: u32 = order_counts.iter().map(|(_, c)| c).sum();
+
+    let mut out = String::new();
+    out.push_str(&format!("# NUMBER ALTERNATIVES: {}\n", candidates.len()));
+    out.push_str(&format!("# NUMBER VOTERS: {}\n", voters));
+    out.push_str(&format!("# NUMBER UNIQUE ORDERS: {}\n", order_counts.len()));
+    for (i, candidate) in candidates.iter().enumerate() {
+        out.push_str(&format!("# ALTERNATIVE NAME {}: {}\n", i + 1, candidate.name));
+    }
+    for (order, count) in &order_counts {
+        out.push_str(&format!("{}: {}\n", count, order));
+    }
+
+    out
+}
+
+/// Write an [`Election`] to a PrefLib file on disk.
+pub fn write_preflib_file(election: &Election, path: &Path) {
+    let contents = write_preflib(election);
+    let mut file = File::create(path)
+        .unwrap_or_else(|e| panic!("Failed to create PrefLib file {}: {}", path.display(), e));
+    file.write_all(contents.as_bytes())
+        .unwrap_or_else(|e| panic!("Failed to write PrefLib file {}: {}", path.display(), e));
+}
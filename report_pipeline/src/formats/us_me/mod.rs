@@ -6,8 +6,14 @@ use std::collections::BTreeMap;
 use std::path::Path;
 use xl::{ExcelValue, Workbook};
 
+/// Column index of the first ranking column (0 = ballot id, 1–2 are metadata).
+const FIRST_RANK_COL: usize = 3;
+
 struct ReaderOptions {
     files: Vec<String>,
+    /// Optional cap on the number of ranking columns read. When unset the
+    /// depth is taken from the header row instead of a fixed window.
+    max_rankings: Option<u32>,
 }
 
 impl ReaderOptions {
@@ -19,7 +25,12 @@ impl ReaderOptions {
             .map(|x| x.to_string())
             .collect();
 
-        ReaderOptions { files }
+        let max_rankings: Option<u32> = params.get("maxRankings").and_then(|v| v.parse().ok());
+
+        ReaderOptions {
+            files,
+            max_rankings,
+        }
     }
 }
 
@@ -61,7 +72,22 @@ pub fn maine_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Ele
         let sheet = sheets.get(1).unwrap(); // Get the first sheet by position (1-based indexing)
 
         let mut rows = sheet.rows(&mut workbook);
-        rows.next(); // Skip header row
+
+        // Detect the ranking columns from the header row rather than guessing a
+        // fixed window: every labelled column from the first rank column up to
+        // the last non-empty header cell is a ranking. An optional `maxRankings`
+        // caps the depth for elections that pad extra unused columns.
+        let header = rows.next().expect("Maine worksheet has no header row");
+        let mut last_rank_col = FIRST_RANK_COL;
+        for (i, cell) in header.0.iter().enumerate() {
+            if i >= FIRST_RANK_COL && !matches!(&cell.value, ExcelValue::None) {
+                last_rank_col = i + 1;
+            }
+        }
+        if let Some(max) = options.max_rankings {
+            last_rank_col = last_rank_col.min(FIRST_RANK_COL + max as usize);
+        }
+
         for row in rows {
             let id = if let ExcelValue::Number(id_val) = row[0].value {
                 id_val as u32
@@ -70,17 +96,9 @@ pub fn maine_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Ele
             };
 
             let mut choices = Vec::new();
-            // Process columns 3 onwards (assuming ballot ID is in column 0, and some other data in 1-2)
-            // Use a reasonable upper bound, but be safe about bounds
-            for i in 3..10 {
-                // Try to access the cell safely
-                let cand = if i < 6 {
-                    // Conservative bound - only process columns 3, 4, 5
-                    if let ExcelValue::String(candidate) = &row[i as u16].value {
-                        candidate.as_ref()
-                    } else {
-                        "undervote"
-                    }
+            for i in FIRST_RANK_COL..last_rank_col {
+                let cand = if let ExcelValue::String(candidate) = &row[i as u16].value {
+                    candidate.as_ref()
                 } else {
                     "undervote"
                 };
@@ -1,6 +1,5 @@
 use crate::formats::common::CandidateMap;
 use crate::model::election::{Ballot, Candidate, CandidateType, Choice, Election};
-use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::read_dir;
@@ -19,11 +18,15 @@ fn create_nyc_worksheet() -> Worksheet {
     )
 }
 
+/// Default number of ranked choices on an NYC RCV ballot.
+const DEFAULT_MAX_RANKINGS: u32 = 5;
+
 struct ReaderOptions {
     office_name: String,
     jurisdiction_name: String,
     candidates_file: String,
     cvr_pattern: String,
+    max_rankings: u32,
 }
 
 impl ReaderOptions {
@@ -36,11 +39,19 @@ impl ReaderOptions {
 
         let cvr_pattern: String = params.get("cvrPattern").unwrap().clone();
 
+        // Elections with 6+ ranked choices are increasingly common; the depth
+        // defaults to five but can be overridden per contest.
+        let max_rankings: u32 = params
+            .get("maxRankings")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_RANKINGS);
+
         ReaderOptions {
             office_name,
             candidates_file,
             jurisdiction_name,
             cvr_pattern,
+            max_rankings,
         }
     }
 }
@@ -71,15 +82,26 @@ fn scan_worksheets_for_race(
     office_name: &str,
     jurisdiction_name: &str,
     cvr_pattern: &str,
+    max_rankings: u32,
     candidates: &HashMap<u32, String>,
 ) -> (HashSet<String>, Vec<Ballot>, CandidateMap<u32>) {
     let mut eligible_precincts: HashSet<String> = HashSet::new();
-    let mut ballots: Vec<Ballot> = Vec::new();
     let mut candidate_ids: CandidateMap<u32> = CandidateMap::new();
-    lazy_static! {
-        static ref COLUMN_RX: Regex =
-            Regex::new(r#"(.+) Choice ([1-5]) of ([1-5]) (.+) \((\d+)\)"#).unwrap();
-    }
+    // Accumulate identical preference orders into a single weighted ballot.
+    // NYC council races run to hundreds of thousands of rows, most of them
+    // byte-identical orderings; aggregating by the `Vec<Choice>` keeps one
+    // weighted ballot per distinct order instead of one per row. The first
+    // CVR id seen for an order is retained as its representative id, and a
+    // parallel list preserves first-seen order for deterministic output.
+    // Keyed on (preference order, grouping key) so ballots from different
+    // precincts stay distinct and remain aggregable per geographic unit.
+    let mut order_weights: HashMap<(Vec<Choice>, Option<String>), (String, u32)> = HashMap::new();
+    let mut order_sequence: Vec<(Vec<Choice>, Option<String>)> = Vec::new();
+    // The rank digits are matched generically and validated against
+    // `max_rankings`, so a contest with more than five columns parses when
+    // `maxRankings` covers its depth; a rank above `max_rankings` still trips
+    // the bound assertion below.
+    let column_rx = Regex::new(r#"(.+) Choice (\d+) of (\d+) (.+) \((\d+)\)"#).unwrap();
 
     let file_rx = Regex::new(&format!("^{}$", cvr_pattern)).unwrap();
 
@@ -113,7 +135,7 @@ fn scan_worksheets_for_race(
                     cvr_id_col = Some(i);
                 } else if colname == "Precinct" {
                     precinct_col = Some(i);
-                } else if let Some(caps) = COLUMN_RX.captures(&colname) {
+                } else if let Some(caps) = column_rx.captures(&colname) {
                     if caps.get(1).unwrap().as_str() != office_name {
                         continue;
                     }
@@ -121,7 +143,7 @@ fn scan_worksheets_for_race(
                         continue;
                     }
                     let rank: u32 = caps.get(2).unwrap().as_str().parse().unwrap();
-                    assert!((1..=5).contains(&rank));
+                    assert!((1..=max_rankings).contains(&rank));
                     rank_to_col.insert(rank, i);
                 }
             }
@@ -136,10 +158,14 @@ fn scan_worksheets_for_race(
                 continue; // Skip if ballot ID is not a string
             };
 
-            // Check if this ballot is from an eligible precinct and collect votes
+            // Check if this ballot is from an eligible precinct and collect votes.
+            // The precinct label is retained as the ballot's grouping key so
+            // results can later be aggregated per geographic unit.
             let mut has_votes = false;
+            let mut ballot_group: Option<String> = None;
             if let Some(precinct_col_idx) = precinct_col {
                 if let ExcelValue::String(precinct) = &row[precinct_col_idx as u16].value {
+                    ballot_group = Some(precinct.to_string());
                     // Check if this ballot has any votes for this council district
                     for col in rank_to_col.values() {
                         if let ExcelValue::String(value) = &row[*col as u16].value {
@@ -186,11 +212,27 @@ fn scan_worksheets_for_race(
                 votes.push(choice);
             }
 
-            let ballot = Ballot::new(ballot_id, votes);
-            ballots.push(ballot);
+            let key = (votes, ballot_group);
+            order_weights
+                .entry(key.clone())
+                .and_modify(|(_, weight)| *weight += 1)
+                .or_insert_with(|| {
+                    order_sequence.push(key);
+                    (ballot_id, 1)
+                });
         }
     }
 
+    // Emit one weighted ballot per distinct (order, group), in first-seen order.
+    let ballots: Vec<Ballot> = order_sequence
+        .into_iter()
+        .map(|key| {
+            let (ballot_id, weight) = order_weights.remove(&key).unwrap();
+            let (votes, group) = key;
+            Ballot::with_weight_and_group(ballot_id, votes, weight, group)
+        })
+        .collect();
+
     (eligible_precincts, ballots, candidate_ids)
 }
 
@@ -220,6 +262,7 @@ pub fn nyc_ballot_reader(path: &Path, params: BTreeMap<String, String>) -> Elect
         &options.office_name,
         &options.jurisdiction_name,
         &options.cvr_pattern,
+        options.max_rankings,
         &candidates,
     );
 
@@ -1,3 +1,4 @@
+use crate::formats::con::read_constraints_file;
 use crate::formats::read_election;
 use crate::model::election::{
     CandidateId, CandidateType, Election, ElectionInfo, ElectionPreprocessed, NormalizedBallot,
@@ -5,6 +6,13 @@ use crate::model::election::{
 use crate::model::metadata::{Contest, ElectionMetadata, Jurisdiction};
 use crate::model::report::{CandidatePairEntry, CandidatePairTable, CandidateVotes, ContestReport, RankingDistribution};
 use crate::normalizers::normalize_election;
+use crate::tabulator::constraints::{Constraints, GuardStatus};
+use crate::tabulator::equal_ranking::{tally, GroupedBallot};
+use crate::tabulator::number::{NumberKind, Number, Rational};
+use crate::tabulator::scottish::WeightedBallot;
+use crate::tabulator::stage_log::build_stage_log;
+use crate::tabulator::tiebreak::{break_tie, TieBreak, TieBreakRecord};
+use crate::tabulator::transfer::{tabulate_stv, TransferMethod};
 use crate::tabulator::{tabulate, Allocatee, TabulatorRound};
 use colored::*;
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -17,6 +25,70 @@ pub fn winner(rounds: &[TabulatorRound]) -> Option<CandidateId> {
         .and_then(|allocation| allocation.allocatee.candidate_id())
 }
 
+/// Compute the Droop quota for a multi-seat contest:
+/// `floor(valid_ballots / (seats + 1)) + 1`.
+pub fn droop_quota(valid_ballots: u32, seats: u32) -> u32 {
+    valid_ballots / (seats + 1) + 1
+}
+
+/// The set of candidates elected by a quota-based count, in the order they
+/// secured a seat.
+///
+/// Taking the top `seats` candidates by final-round votes is wrong for STV: a
+/// candidate's total is reduced back to roughly the quota once their surplus is
+/// transferred away, so the final standings no longer rank the elected set.
+/// Instead, a candidate is elected the first round their running total reaches
+/// the Droop quota; seats still open when the count terminates (the field was
+/// thinned to exactly the number of seats without everyone reaching quota) are
+/// filled from the final round's continuing candidates by descending total.
+/// For a single-seat contest this reduces to the lone [`winner`].
+pub fn winners(rounds: &[TabulatorRound], seats: u32) -> Vec<CandidateId> {
+    let Some(first_round) = rounds.first() else {
+        return vec![];
+    };
+
+    let valid: u32 = first_round
+        .allocations
+        .iter()
+        .filter_map(|a| match a.allocatee {
+            Allocatee::Candidate(_) => Some(a.votes),
+            Allocatee::Exhausted => None,
+        })
+        .sum();
+    let quota = droop_quota(valid, seats);
+
+    let mut elected: Vec<CandidateId> = Vec::new();
+    for round in rounds {
+        for alloc in &round.allocations {
+            if let Allocatee::Candidate(c) = alloc.allocatee {
+                if alloc.votes >= quota && !elected.contains(&c) {
+                    elected.push(c);
+                }
+            }
+        }
+        if elected.len() >= seats as usize {
+            break;
+        }
+    }
+
+    if elected.len() < seats as usize {
+        if let Some(final_round) = rounds.last() {
+            let mut remaining: Vec<(CandidateId, u32)> = final_round
+                .allocations
+                .iter()
+                .filter_map(|a| a.allocatee.candidate_id().map(|c| (c, a.votes)))
+                .filter(|(c, _)| !elected.contains(c))
+                .collect();
+            remaining.sort_by_key(|(_, votes)| std::cmp::Reverse(*votes));
+            for (c, _) in remaining.into_iter().take(seats as usize - elected.len()) {
+                elected.push(c);
+            }
+        }
+    }
+
+    elected
+}
+
 pub fn total_votes(rounds: &[TabulatorRound]) -> Vec<CandidateVotes> {
     let candidate_to_initial_votes: BTreeMap<CandidateId, u32> = rounds[0]
         .allocations
@@ -59,6 +131,65 @@ pub fn total_votes(rounds: &[TabulatorRound]) -> Vec<CandidateVotes> {
     result
 }
 
+/// Per-round vote accounting, reconciling candidate totals against the
+/// exhausted pile and any rounding loss so every vote is accounted for.
+///
+/// In a transfer-based count, candidate votes + exhausted + loss-by-fraction
+/// must equal the round's total continuing votes. `loss_by_fraction` captures
+/// the fraction shed when fractional transfer values are truncated; it is zero
+/// under the integer count (a `u32` total cannot lose a fraction), and
+/// `reconciles` flags a round whose columns fail to add up.
+#[derive(Debug, Clone)]
+pub struct RoundReconciliation {
+    pub round: u32,
+    pub candidate_votes: u32,
+    pub exhausted: u32,
+    pub loss_by_fraction: u32,
+    pub total: u32,
+    pub reconciles: bool,
+}
+
+/// Reconcile each round's candidate totals, exhausted votes, and rounding loss
+/// against the count's total, taking the opening round's total as the baseline
+/// every later round must still sum to.
+pub fn reconcile_rounds(rounds: &[TabulatorRound]) -> Vec<RoundReconciliation> {
+    let baseline: u32 = rounds
+        .first()
+        .map(|round| round.allocations.iter().map(|a| a.votes).sum())
+        .unwrap_or(0);
+
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(i, round)| {
+            let mut candidate_votes = 0u32;
+            let mut exhausted = 0u32;
+            for alloc in &round.allocations {
+                match alloc.allocatee {
+                    Allocatee::Candidate(_) => candidate_votes += alloc.votes,
+                    Allocatee::Exhausted => exhausted += alloc.votes,
+                }
+            }
+
+            // Integer rounds cannot shed a fractional vote, so loss-by-fraction
+            // is zero here; any gap between the baseline and what the round
+            // accounts for is untracked ballots, which must surface as a
+            // failure to reconcile rather than be silently absorbed as loss.
+            let accounted = candidate_votes + exhausted;
+            let loss_by_fraction = 0;
+
+            RoundReconciliation {
+                round: (i + 1) as u32,
+                candidate_votes,
+                exhausted,
+                loss_by_fraction,
+                total: baseline,
+                reconciles: accounted + loss_by_fraction == baseline,
+            }
+        })
+        .collect()
+}
+
 pub fn generate_pairwise_counts(
     candidates: &[CandidateId],
     ballots: &[NormalizedBallot],
@@ -89,6 +220,119 @@ pub fn generate_pairwise_counts(
     preference_map
 }
 
+/// How a ballot's equal rankings (a tie group at one rank) are treated during
+/// normalization. Selected via the `equal_ranking` loader parameter; [`EqualRankingMode::Skip`]
+/// preserves the historical overvote behavior by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EqualRankingMode {
+    /// Honor the tie: count equal-ranked pairs as a mutual tie and split a
+    /// shared first preference fractionally across the tied candidates.
+    Honor,
+    /// Skip the tied rank, advancing to the next rank (legacy overvote).
+    Skip,
+    /// Accepted as `spoil`; ballots carrying an equal ranking are counted
+    /// exactly as under [`EqualRankingMode::Skip`], and none is discarded.
+    Spoil,
+}
+
+impl Default for EqualRankingMode {
+    fn default() -> EqualRankingMode {
+        EqualRankingMode::Skip
+    }
+}
+
+impl EqualRankingMode {
+    /// Parse the `equal_ranking` option, defaulting to the legacy skip/overvote
+    /// behavior. `honor` preserves tie groups and counts them fractionally;
+    /// `spoil` is recognized but handled like `skip`: no ballot is discarded.
+    pub fn from_option(value: Option<&String>) -> EqualRankingMode {
+        match value.map(|s| s.to_lowercase()).as_deref() {
+            Some("honor") | Some("honour") => EqualRankingMode::Honor,
+            Some("spoil") => EqualRankingMode::Spoil,
+            _ => EqualRankingMode::Skip,
+        }
+    }
+}
+
+/// Pairwise counts from ballots whose ranks may hold equal-ranked groups.
+///
+/// Each ballot is a list of ranks, each rank a set of candidates tied at that
+/// position. A candidate ranked strictly above another contributes a full
+/// directional preference as usual; two candidates sharing a rank are recorded
+/// as a mutual tie that increments neither direction but still counts toward
+/// the pair total, so margins and turnout stay consistent. Candidates a ballot
+/// omits sit below every ranked candidate.
+pub fn generate_pairwise_counts_grouped(
+    candidates: &[CandidateId],
+    ballots: &[Vec<Vec<CandidateId>>],
+) -> (
+    HashMap<(CandidateId, CandidateId), u32>,
+    HashMap<(CandidateId, CandidateId), u32>,
+) {
+    let mut directional: HashMap<(CandidateId, CandidateId), u32> = HashMap::new();
+    let mut pair_total: HashMap<(CandidateId, CandidateId), u32> = HashMap::new();
+    let all_candidates: HashSet<CandidateId> = candidates.iter().copied().collect();
+
+    for ranks in ballots {
+        let mut above: Vec<CandidateId> = Vec::new();
+        let mut ranked: HashSet<CandidateId> = HashSet::new();
+
+        for group in ranks {
+            // Everyone strictly above this rank beats everyone in it.
+            for &winner in &above {
+                for &loser in group {
+                    *directional.entry((winner, loser)).or_insert(0) += 1;
+                    *pair_total.entry((winner, loser)).or_insert(0) += 1;
+                }
+            }
+            // Candidates sharing this rank are a mutual tie: pair total only.
+            for (i, &a) in group.iter().enumerate() {
+                for &b in &group[i + 1..] {
+                    *pair_total.entry((a, b)).or_insert(0) += 1;
+                    *pair_total.entry((b, a)).or_insert(0) += 1;
+                }
+            }
+            for &c in group {
+                above.push(c);
+                ranked.insert(c);
+            }
+        }
+
+        // Ranked candidates beat every unranked one.
+        for unranked in all_candidates.difference(&ranked) {
+            for &winner in &above {
+                *directional.entry((winner, *unranked)).or_insert(0) += 1;
+                *pair_total.entry((winner, *unranked)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    (directional, pair_total)
+}
+
+/// Fractional first-preference shares when the top rank may be a tie group.
+///
+/// A ballot whose first rank holds `k` tied candidates contributes `1/k` of a
+/// first preference to each, so the shared intent is attributed proportionally
+/// rather than discarded. Returned as floats keyed by candidate.
+pub fn generate_first_preference_shares(
+    ballots: &[Vec<Vec<CandidateId>>],
+) -> HashMap<CandidateId, f64> {
+    let mut shares: HashMap<CandidateId, f64> = HashMap::new();
+    for ranks in ballots {
+        if let Some(first) = ranks.first() {
+            if first.is_empty() {
+                continue;
+            }
+            let share = 1.0 / first.len() as f64;
+            for &candidate in first {
+                *shares.entry(candidate).or_insert(0.0) += share;
+            }
+        }
+    }
+    shares
+}
+
 pub fn generate_pairwise_preferences(
     candidates: &[CandidateId],
     preference_map: &HashMap<(CandidateId, CandidateId), u32>,
@@ -325,6 +569,144 @@ pub fn smith_set(
     last_set
 }
 
+/// Compute a full social ranking by the Schulze beatpath method.
+///
+/// The Smith set pins down a Condorcet winner only when it is a singleton;
+/// with a majority cycle it yields no ordering. Schulze completes the picture:
+/// from the pairwise counts it forms the strongest-path strengths and orders
+/// candidates so that the winner beats or ties every other. The result is a
+/// `schulze_ranking` on [`ContestReport`], reconciled against the IRV winner.
+///
+/// Concretely, `d[i][j]` is the number of voters preferring `i` to `j`; the
+/// path strength matrix starts at `p[i][j] = d[i][j]` where `i` beats `j` and
+/// `0` otherwise, then a Floyd–Warshall widest-path pass sets
+/// `p[i][j] = max(p[i][j], min(p[i][k], p[k][j]))`. Candidate `i` is ranked
+/// above `j` iff `p[i][j] > p[j][i]`.
+pub fn schulze_ranking(
+    candidates: &[CandidateId],
+    preference_map: &HashMap<(CandidateId, CandidateId), u32>,
+) -> Vec<CandidateId> {
+    let n = candidates.len();
+    let mut p = vec![vec![0u32; n]; n];
+
+    // Initialize strongest paths with the direct margins where i beats j.
+    for (i, ci) in candidates.iter().enumerate() {
+        for (j, cj) in candidates.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let dij = *preference_map.get(&(*ci, *cj)).unwrap_or(&0);
+            let dji = *preference_map.get(&(*cj, *ci)).unwrap_or(&0);
+            p[i][j] = if dij > dji { dij } else { 0 };
+        }
+    }
+
+    // Floyd–Warshall widest path.
+    for k in 0..n {
+        for i in 0..n {
+            if i == k {
+                continue;
+            }
+            for j in 0..n {
+                if j == i || j == k {
+                    continue;
+                }
+                let candidate = p[i][k].min(p[k][j]);
+                if candidate > p[i][j] {
+                    p[i][j] = candidate;
+                }
+            }
+        }
+    }
+
+    // Order by the beatpath relation: i outranks j iff p[i][j] > p[j][i].
+    let mut ranking: Vec<usize> = (0..n).collect();
+    ranking.sort_by(|&i, &j| p[j][i].cmp(&p[i][j]));
+    ranking.into_iter().map(|i| candidates[i]).collect()
+}
+
+/// Reconstruct the tie-breaks that the count resolved, for the report's audit
+/// trail.
+///
+/// A ballot elimination between two rounds is a tie whenever more than one
+/// continuing candidate shared the equal-lowest total in the round that decided
+/// it. For each such elimination this re-runs the configured [`TieBreak`] over
+/// the rounds counted up to that point and records the decision, so consumers
+/// can see which strategy eliminated whom and why.
+fn detect_tie_breaks(rounds: &[TabulatorRound], strategy: &TieBreak) -> Vec<TieBreakRecord> {
+    let mut records = Vec::new();
+
+    for (i, round) in rounds.iter().enumerate() {
+        // The candidates eliminated leaving this round are those whose ballots
+        // transfer out in the next round.
+        let Some(next) = rounds.get(i + 1) else {
+            continue;
+        };
+        let eliminated: HashSet<CandidateId> = next.transfers.iter().map(|t| t.from).collect();
+        if eliminated.is_empty() {
+            continue;
+        }
+
+        // The equal-lowest group among this round's continuing candidates.
+        let totals: Vec<(CandidateId, u32)> = round
+            .allocations
+            .iter()
+            .filter_map(|a| a.allocatee.candidate_id().map(|c| (c, a.votes)))
+            .collect();
+        let Some(min_votes) = totals.iter().map(|(_, v)| *v).min() else {
+            continue;
+        };
+        let tied: Vec<CandidateId> = totals
+            .iter()
+            .filter(|(_, v)| *v == min_votes)
+            .map(|(c, _)| *c)
+            .collect();
+
+        if tied.len() >= 2 && tied.iter().any(|c| eliminated.contains(c)) {
+            records.push(break_tie(&tied, &rounds[..=i], strategy));
+        }
+    }
+
+    records
+}
+
+/// Evaluate representation constraints against the elected set.
+///
+/// Tallies, per category, how many of its candidates were elected versus are
+/// still available, then asks [`Constraints::guard_status`] what the rules
+/// require of each candidate. Returns the non-[`GuardStatus::Free`] verdicts so
+/// the report can disclose exactly which candidates a constraint guarded or
+/// blocked.
+fn evaluate_constraints(
+    constraints: &Constraints,
+    elected: &[CandidateId],
+    candidates: &[CandidateId],
+) -> Vec<(CandidateId, GuardStatus)> {
+    let mut elected_by_category: HashMap<String, u32> = HashMap::new();
+    let mut available_by_category: HashMap<String, u32> = HashMap::new();
+    for candidate in candidates {
+        for key in constraints.categories_of(*candidate) {
+            if elected.contains(candidate) {
+                *elected_by_category.entry(key.clone()).or_insert(0) += 1;
+            } else {
+                *available_by_category.entry(key.clone()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let status =
+                constraints.guard_status(*candidate, &elected_by_category, &available_by_category);
+            match status {
+                GuardStatus::Free => None,
+                _ => Some((*candidate, status)),
+            }
+        })
+        .collect()
+}
+
 /// Generate a `ContestReport` from preprocessed election data.
 pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
     let ballots = &election.ballots.ballots;
@@ -336,8 +718,12 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
             ballot_count: 0,
             candidates: election.ballots.candidates.clone(),
             winner: None,
+            winners: vec![],
             num_candidates: 0,
             rounds: vec![],
+            reconciliation: vec![],
+            tie_breaks: vec![],
+            stage_log: vec![],
             total_votes: vec![],
             pairwise_preferences: CandidatePairTable {
                 entries: vec![],
@@ -362,9 +748,27 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
             }),
             smith_set: vec![],
             condorcet: None,
+            schulze_ranking: vec![],
+            first_preference_shares: None,
+            fractional_first_preferences: None,
+            constraints_active: false,
+            constraint_status: vec![],
+            number_precision: None,
         };
     }
 
+    // Per-contest tabulation options carried in `loader_params`: the numeric
+    // backend, seat count, surplus-transfer method, tie-break strategy, and an
+    // optional constraints file. Absent entries fall back to the historical
+    // single-winner, float-arithmetic defaults.
+    let params = election.info.loader_params.clone().unwrap_or_default();
+    let number_kind = NumberKind::from_option(params.get("numbers"));
+    let seats: u32 = params
+        .get("seats")
+        .and_then(|s| s.parse().ok())
+        .filter(|s| *s >= 1)
+        .unwrap_or(1);
+
     eprintln!("  - Tabulating rounds...");
     let rounds = tabulate(ballots, &election.info.tabulation_options);
     let winner = winner(&rounds);
@@ -375,15 +779,169 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
         .filter(|d| d.candidate_type != CandidateType::WriteIn)
         .count() as u32;
 
+    eprintln!("  - Recording tie-breaks...");
+    let tie_break = TieBreak::from_options(params.get("tie_break"), params.get("tie_break_seed"));
+    let tie_breaks = detect_tie_breaks(&rounds, &tie_break);
+    for record in &tie_breaks {
+        eprintln!("    {}", record.round_note());
+    }
+
+    eprintln!("  - Reconciling per-round vote accounting...");
+    let reconciliation = reconcile_rounds(&rounds);
+    for round in &reconciliation {
+        if !round.reconciles {
+            eprintln!(
+                "{}",
+                format!(
+                    "Round {} does not reconcile: {} candidate + {} exhausted != {}",
+                    round.round, round.candidate_votes, round.exhausted, round.total
+                )
+                .purple()
+            );
+        }
+    }
+
     eprintln!("  - Calculating total votes...");
     let total_votes = total_votes(&rounds);
     let mut candidates: Vec<CandidateId> = total_votes.iter().map(|d| d.candidate).collect();
     candidates.sort(); // Ensure consistent ordering
     eprintln!("  - Found {} candidates", candidates.len());
 
+    // Representation constraints, if a CON file was referenced for this contest.
+    // They are read through the format layer during preprocessing (see
+    // `preprocess_election`) and enforced inside the multi-seat count, which
+    // passes over a blocked candidate for election and a guarded one for
+    // exclusion while any other candidate remains to take that step.
+    let constraints = election.constraints.as_ref().filter(|c| !c.is_empty());
+    let constraints_active = constraints.is_some();
+
+    // The elected set. A single-seat contest is the lone IRV winner; a
+    // multi-seat contest runs a proportional STV count against the Droop quota
+    // with fractional surplus transfers, dispatched through `tabulate_stv` to
+    // the configured transfer method (Weighted Inclusive Gregory or Meek) and
+    // honoring any representation constraints.
+    let winners = if seats > 1 {
+        let transfer_method = TransferMethod::from_param(params.get("transfer_method"));
+        let weighted: Vec<WeightedBallot> = ballots
+            .iter()
+            .map(|b| WeightedBallot {
+                preferences: b.choices(),
+                weight: 1,
+            })
+            .collect();
+        tabulate_stv(transfer_method, &candidates, &weighted, seats, constraints)
+            .elected()
+            .to_vec()
+    } else {
+        winners(&rounds, seats)
+    };
+
+    // The guard/block verdicts against the final elected set are surfaced so
+    // jurisdictions running quota-based representation rules can audit them.
+    let constraint_status: Vec<(CandidateId, String)> = constraints
+        .map(|c| {
+            evaluate_constraints(c, &winners, &candidates)
+                .into_iter()
+                .map(|(candidate, status)| {
+                    let label = match status {
+                        GuardStatus::Guarded => "guarded",
+                        GuardStatus::Blocked => "blocked",
+                        GuardStatus::Free => "free",
+                    };
+                    (candidate, label.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    eprintln!("  - Building structured stage log...");
+    // Distill the rounds into a structured per-stage log for report.json. The
+    // quota is disclosed for multi-seat counts (Droop over the first round's
+    // valid ballots) and omitted for single-winner IRV.
+    let quota = if seats > 1 {
+        rounds.first().map(|round| {
+            let valid: u32 = round
+                .allocations
+                .iter()
+                .filter_map(|a| match a.allocatee {
+                    Allocatee::Candidate(_) => Some(a.votes),
+                    Allocatee::Exhausted => None,
+                })
+                .sum();
+            droop_quota(valid, seats)
+        })
+    } else {
+        None
+    };
+    let mut stage_log = build_stage_log(&rounds, quota);
+
+    // Attach the audit notes the console emits to the stage they describe: each
+    // tie-break to the stage whose elimination it decided, and any
+    // representation-constraint verdicts to the final stage.
+    for record in &tie_breaks {
+        if let Some(index) = rounds
+            .iter()
+            .position(|r| r.transfers.iter().any(|t| t.from == record.selected))
+        {
+            if let Some(stage) = stage_log.get_mut(index) {
+                stage.note = Some(record.round_note());
+            }
+        }
+    }
+    if !constraint_status.is_empty() {
+        if let Some(last) = stage_log.last_mut() {
+            let verdicts = constraint_status
+                .iter()
+                .map(|(candidate, label)| format!("candidate {} {}", candidate.0, label))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let note = format!("representation constraints: {}", verdicts);
+            last.note = match last.note.take() {
+                Some(existing) => Some(format!("{}; {}", existing, note)),
+                None => Some(note),
+            };
+        }
+    }
+
     eprintln!("  - Generating pairwise counts...");
-    let pairwise_counts: HashMap<(CandidateId, CandidateId), u32> =
-        generate_pairwise_counts(&candidates, ballots);
+    // When equal rankings are honored, count tied pairs as mutual ties through
+    // the grouped analysis; otherwise use the strict single-candidate-per-rank
+    // path. The grouped ballots are the normalized orders expressed as ranks of
+    // candidate sets, where a `Choice::EqualGroup` surfaces as a rank with more
+    // than one member and every other rank is a singleton.
+    let equal_ranking = EqualRankingMode::from_option(params.get("equal_ranking"));
+    let (pairwise_counts, first_preference_shares) = match equal_ranking {
+        EqualRankingMode::Honor => {
+            let grouped: Vec<Vec<Vec<CandidateId>>> =
+                ballots.iter().map(|b| b.grouped_choices()).collect();
+            let (directional, _pair_total) =
+                generate_pairwise_counts_grouped(&candidates, &grouped);
+            let shares = generate_first_preference_shares(&grouped);
+            (directional, Some(shares))
+        }
+        EqualRankingMode::Skip | EqualRankingMode::Spoil => {
+            (generate_pairwise_counts(&candidates, ballots), None)
+        }
+    };
+
+    // Under honor mode, compute the exact fractional first-preference tally,
+    // which splits each ballot's value equally across the members of its
+    // highest tied rank. Every candidate is active in this tally; no candidate
+    // is eliminated here, and the round-by-round count does not split ballots.
+    let fractional_first_preferences = if equal_ranking == EqualRankingMode::Honor {
+        let grouped: Vec<GroupedBallot> = ballots
+            .iter()
+            .map(|b| GroupedBallot {
+                ranks: b.grouped_choices(),
+                value: Rational::from_int(1),
+            })
+            .collect();
+        let (totals, exhausted) = tally(&grouped, &candidates);
+        eprintln!("    equal-ranking exhausted value: {:.5}", exhausted.to_f64());
+        Some(totals.into_iter().map(|(c, v)| (c, v.to_f64())).collect::<Vec<_>>())
+    } else {
+        None
+    };
 
     eprintln!("  - Generating pairwise preferences...");
     let pairwise_preferences = generate_pairwise_preferences(&candidates, &pairwise_counts);
@@ -404,6 +962,15 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
         eprintln!("{}", "Non-condorcet!".purple());
     }
 
+    // Schulze completes a ranking even through a majority cycle; reconcile its
+    // winner (the head of the beatpath order) against the IRV winner.
+    let schulze = schulze_ranking(&candidates, &pairwise_counts);
+    if let (Some(irv), Some(schulze_winner)) = (winner, schulze.first().copied()) {
+        if irv != schulze_winner {
+            eprintln!("{}", "Non-condorcet! (Schulze disagrees)".purple());
+        }
+    }
+
     eprintln!("  - Generating first alternate matrix...");
     let first_alternate = generate_first_alternate(&candidates, ballots);
 
@@ -439,8 +1006,12 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
         ballot_count: election.ballots.ballots.len() as u32,
         candidates: election.ballots.candidates.clone(),
         winner,
+        winners,
         num_candidates,
         rounds,
+        reconciliation,
+        tie_breaks,
+        stage_log,
         total_votes: sorted_total_votes,
         pairwise_preferences,
         first_alternate,
@@ -448,6 +1019,12 @@ pub fn generate_report(election: &ElectionPreprocessed) -> ContestReport {
         ranking_distribution: Some(ranking_distribution),
         smith_set: sorted_smith_set,
         condorcet,
+        schulze_ranking: schulze,
+        first_preference_shares,
+        fractional_first_preferences,
+        constraints_active,
+        constraint_status,
+        number_precision: number_kind.reported_precision(),
     }
 }
 
@@ -467,6 +1044,7 @@ pub fn preprocess_election(
     );
     let office = ec.offices.get(&contest.office).unwrap();
 
+    let constraints = load_constraints(&contest.loader_params, &election);
     let normalized_election = normalize_election(&metadata.normalization, election);
 
     ElectionPreprocessed {
@@ -485,9 +1063,26 @@ pub fn preprocess_election(
             website: metadata.website.clone(),
         },
         ballots: normalized_election,
+        constraints,
     }
 }
 
+/// Load this contest's representation constraints, if its `loader_params`
+/// reference a CON file, reading them through the format-layer CON reader so a
+/// single grammar and eligibility rule govern every constraint source. No
+/// withdrawn ids are passed to the reader here, so only write-in candidates are
+/// excluded from category membership.
+fn load_constraints(
+    loader_params: &Option<BTreeMap<String, String>>,
+    election: &Election,
+) -> Option<Constraints> {
+    loader_params
+        .as_ref()
+        .and_then(|params| params.get("constraints"))
+        .map(|path| read_constraints_file(Path::new(path), election, &[]))
+        .filter(|constraints| !constraints.is_empty())
+}
+
 /// Preprocess an election from already-loaded election data
 /// This is used for batch processing where elections are loaded once and reused
 pub fn preprocess_election_from_data(
@@ -497,6 +1092,7 @@ pub fn preprocess_election_from_data(
     contest: &Contest,
     election_path: &str,
 ) -> ElectionPreprocessed {
+    let constraints = load_constraints(&contest.loader_params, &election);
     let normalized_election = normalize_election(&metadata.normalization, election);
     let office = jurisdiction.offices.get(&contest.office).unwrap();
 
@@ -516,5 +1112,6 @@ pub fn preprocess_election_from_data(
             website: metadata.website.clone(),
         },
         ballots: normalized_election,
+        constraints,
     }
 }
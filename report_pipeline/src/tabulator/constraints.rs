@@ -0,0 +1,161 @@
+//! Representation constraints for constrained STV counts.
+//!
+//! Some jurisdictions require the elected set to satisfy minimum and maximum
+//! seat counts across categorical dimensions — a regional quota, a gender
+//! balance rule, or a combination such as region × gender. The constraints are
+//! read from a per-contest CON file (referenced by a `constraints` entry in
+//! `loader_params`) by [`crate::formats::con::read_constraints_file`], which
+//! resolves candidate ids and drops ineligible members before assembling them
+//! here via [`Constraints::from_parts`]. This module then answers, at each
+//! elimination or election step, whether the normal path would breach a bound.
+//!
+//! The tabulator consults [`Constraints::guard_status`] before acting: a
+//! candidate in an under-filled category is **guarded** (protected from
+//! elimination, because dropping them could make the category unable to reach
+//! its minimum), and a candidate in a category already at its cap is
+//! **blocked** (must not be elected). Write-in candidates never carry a
+//! category; withdrawn candidates are dropped only when the CON reader is given
+//! their ids.
+//!
+//! Rules that bound *combinations* of dimensions (e.g. region × gender —
+//! "at least one woman from the north") are expressed as composite categories:
+//! a candidate simply belongs to every cell it satisfies, so a combined bound
+//! is just another category key. This keeps a single guard/block decision for
+//! both single- and multi-dimensional constraints rather than a parallel
+//! tensor representation.
+
+use crate::model::election::CandidateId;
+use std::collections::{BTreeMap, HashMap};
+
+/// A single category with its seat bounds, e.g. `region:north 1 3`.
+#[derive(Debug, Clone)]
+pub struct Category {
+    /// The dimension this category belongs to (e.g. `region`).
+    pub dimension: String,
+    /// The value within that dimension (e.g. `north`).
+    pub value: String,
+    /// Minimum seats the category must win.
+    pub min: u32,
+    /// Maximum seats the category may win.
+    pub max: u32,
+}
+
+impl Category {
+    /// The fully-qualified key `dimension:value` used to index a category.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.dimension, self.value)
+    }
+}
+
+/// What the constraint layer requires of a candidate at a given moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardStatus {
+    /// No constraint currently applies.
+    Free,
+    /// Protected from elimination to keep an under-filled category reachable.
+    Guarded,
+    /// Prevented from election because its category is at capacity.
+    Blocked,
+}
+
+/// The constraints attached to a contest.
+#[derive(Debug, Clone, Default)]
+pub struct Constraints {
+    /// Every category, keyed by `dimension:value`.
+    categories: BTreeMap<String, Category>,
+    /// Category keys each candidate belongs to.
+    memberships: HashMap<CandidateId, Vec<String>>,
+}
+
+impl Constraints {
+    /// Assemble constraints from already-parsed categories and memberships.
+    ///
+    /// This is the sole constructor: the format-side CON reader
+    /// ([`crate::formats::con::read_constraints_file`]) parses the file,
+    /// resolves candidate ids, and applies the write-in/withdrawn exclusion
+    /// before handing the parts here, so there is a single file grammar.
+    pub fn from_parts(
+        categories: BTreeMap<String, Category>,
+        memberships: HashMap<CandidateId, Vec<String>>,
+    ) -> Constraints {
+        Constraints {
+            categories,
+            memberships,
+        }
+    }
+
+    /// Whether any category is declared.
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Decide what the constraints require of `candidate`, given how many seats
+    /// each category has already secured (`elected_by_category`) and how many
+    /// candidates in each category are still available to be elected
+    /// (`available_by_category`).
+    pub fn guard_status(
+        &self,
+        candidate: CandidateId,
+        elected_by_category: &HashMap<String, u32>,
+        available_by_category: &HashMap<String, u32>,
+    ) -> GuardStatus {
+        let Some(keys) = self.memberships.get(&candidate) else {
+            return GuardStatus::Free;
+        };
+
+        for key in keys {
+            let Some(category) = self.categories.get(key) else {
+                continue;
+            };
+            let elected = elected_by_category.get(key).copied().unwrap_or(0);
+            let available = available_by_category.get(key).copied().unwrap_or(0);
+
+            // Already at the cap: electing another would overshoot the maximum.
+            if elected >= category.max {
+                return GuardStatus::Blocked;
+            }
+
+            // Dropping this candidate would leave too few to reach the minimum.
+            if elected + available <= category.min {
+                return GuardStatus::Guarded;
+            }
+        }
+
+        GuardStatus::Free
+    }
+
+    /// Classify `candidate` from the raw elected/available sets, building the
+    /// per-category tallies [`guard_status`](Self::guard_status) needs.
+    ///
+    /// `elected` are the candidates already seated and `available` those still
+    /// in contention. The STV backends call this each stage so a blocked
+    /// candidate is kept out of election and a guarded one out of exclusion.
+    pub fn status_in(
+        &self,
+        candidate: CandidateId,
+        elected: &[CandidateId],
+        available: &[CandidateId],
+    ) -> GuardStatus {
+        let mut elected_by_category: HashMap<String, u32> = HashMap::new();
+        for c in elected {
+            for key in self.categories_of(*c) {
+                *elected_by_category.entry(key.clone()).or_insert(0) += 1;
+            }
+        }
+        let mut available_by_category: HashMap<String, u32> = HashMap::new();
+        for c in available {
+            for key in self.categories_of(*c) {
+                *available_by_category.entry(key.clone()).or_insert(0) += 1;
+            }
+        }
+        self.guard_status(candidate, &elected_by_category, &available_by_category)
+    }
+
+    /// The category keys `candidate` belongs to, if any.
+    pub fn categories_of(&self, candidate: CandidateId) -> &[String] {
+        self.memberships
+            .get(&candidate)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
@@ -0,0 +1,84 @@
+//! Shared/fractional handling of equal-ranked ballot groups.
+//!
+//! The readers emit a tie group like `{1,2}` as a
+//! [`Choice::EqualGroup`](crate::model::election::Choice) preserving its
+//! members, rather than discarding the intent (present in PrefLib TOI/TOC and
+//! similar data) as an overvote. In the equal-rankings tabulation mode a tied
+//! rank is carried through as a set of candidates sharing that rank, and this
+//! module performs the fractional allocation the count needs:
+//!
+//! - When a ballot reaches a tied rank, its current value is split equally
+//!   among the candidates in that group who are still active.
+//! - When one of those candidates is eliminated, re-running the allocation
+//!   redistributes the ballot's value across the remaining active members of
+//!   the same group before it advances to the next rank.
+//!
+//! Overvote handling remains the default; this path runs only when the mode is
+//! enabled. Values are carried as exact [`Rational`]s so the equal splits stay
+//! reproducible.
+
+use super::number::{Number, Rational};
+use crate::model::election::CandidateId;
+
+/// A ballot as a sequence of ranks, each rank a set of candidates tied at that
+/// position (a singleton set is an ordinary strict preference).
+pub struct GroupedBallot {
+    pub ranks: Vec<Vec<CandidateId>>,
+    pub value: Rational,
+}
+
+/// Allocate a single ballot's value to the active candidates it currently
+/// supports, descending its ranks until it finds one containing at least one
+/// active candidate and splitting its value equally among those members.
+///
+/// Returns the per-candidate shares, or an empty vector when no ranked
+/// candidate remains active (the ballot is exhausted).
+pub fn allocate(ballot: &GroupedBallot, active: &[CandidateId]) -> Vec<(CandidateId, Rational)> {
+    for rank in &ballot.ranks {
+        let live: Vec<CandidateId> = rank
+            .iter()
+            .copied()
+            .filter(|c| active.contains(c))
+            .collect();
+
+        if live.is_empty() {
+            // Every candidate at this rank is gone; a tie group's value reflows
+            // naturally by moving on once none of its members remain.
+            continue;
+        }
+
+        // Split equally among the active members of this tied rank.
+        let share = ballot.value.div(&Rational::from_int(live.len() as i64));
+        return live.into_iter().map(|c| (c, share.clone())).collect();
+    }
+
+    Vec::new()
+}
+
+/// Tally the active candidates' fractional first-preference totals across all
+/// ballots, plus the exhausted value whose ballots support nobody active.
+pub fn tally(
+    ballots: &[GroupedBallot],
+    active: &[CandidateId],
+) -> (Vec<(CandidateId, Rational)>, Rational) {
+    let mut totals: Vec<(CandidateId, Rational)> = active
+        .iter()
+        .map(|c| (*c, Rational::zero()))
+        .collect();
+    let mut exhausted = Rational::zero();
+
+    for ballot in ballots {
+        let shares = allocate(ballot, active);
+        if shares.is_empty() {
+            exhausted = exhausted.add(&ballot.value);
+            continue;
+        }
+        for (candidate, share) in shares {
+            if let Some(entry) = totals.iter_mut().find(|(c, _)| *c == candidate) {
+                entry.1 = entry.1.add(&share);
+            }
+        }
+    }
+
+    (totals, exhausted)
+}
@@ -0,0 +1,247 @@
+//! Meek's method STV with iterative keep-value recomputation.
+//!
+//! Meek's method is the gold-standard proportional count that Scottish/Gregory
+//! fractional transfers only approximate. Each candidate carries a keep value
+//! `k` (initially 1). A ballot is "poured" in preference order: starting with
+//! value 1, each candidate in turn retains `value * k` and passes
+//! `value * (1 - k)` to the next preference; whatever remains after the last
+//! preference becomes excess (exhausted).
+//!
+//! Each round sums every candidate's retained votes, sets the quota to
+//! `(total_votes - excess) / (seats + 1)`, and for each currently-elected
+//! candidate updates `k_new = k_old * quota / votes_received`, then re-pours all
+//! ballots and repeats until every elected candidate's votes are within a
+//! caller-supplied convergence tolerance of the quota. Any continuing
+//! candidate reaching quota is elected; if none do and seats remain, the lowest
+//! candidate's keep value is set to 0 (exclusion). The count terminates when the
+//! seats are filled or the continuing candidates equal the remaining seats.
+//!
+//! The count is generic over [`Number`] so callers can choose exact rationals
+//! for reproducibility or floats for speed.
+
+use super::constraints::{Constraints, GuardStatus};
+use super::number::Number;
+use super::scottish::WeightedBallot;
+use crate::model::election::CandidateId;
+use std::collections::BTreeMap;
+
+/// The maximum number of keep-value iterations before a round gives up
+/// converging, a safety valve against pathological inputs.
+const MAX_ITERATIONS: usize = 1000;
+
+/// One stage of the Meek count.
+#[derive(Debug, Clone)]
+pub struct MeekStage {
+    pub description: String,
+    pub totals: BTreeMap<CandidateId, f64>,
+    pub quota: f64,
+    pub excess: f64,
+}
+
+/// The outcome of a Meek STV count.
+#[derive(Debug, Clone)]
+pub struct MeekResult {
+    pub elected: Vec<CandidateId>,
+    pub stages: Vec<MeekStage>,
+}
+
+/// The status of a candidate during the count.
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Hopeful,
+    Elected,
+    Excluded,
+}
+
+/// Pour all ballots through the current keep values, returning each candidate's
+/// retained votes and the total excess (exhausted value).
+fn pour<N: Number>(
+    ballots: &[WeightedBallot],
+    keep: &BTreeMap<CandidateId, N>,
+    status: &BTreeMap<CandidateId, Status>,
+) -> (BTreeMap<CandidateId, N>, N) {
+    let one = N::from_int(1);
+    let mut retained: BTreeMap<CandidateId, N> =
+        keep.keys().map(|c| (*c, N::zero())).collect();
+    let mut excess = N::zero();
+
+    for ballot in ballots {
+        let weight = N::from_int(ballot.weight as i64);
+        let mut value = weight;
+
+        for candidate in &ballot.preferences {
+            match status.get(candidate) {
+                Some(Status::Excluded) | None => continue,
+                _ => {}
+            }
+            let k = keep.get(candidate).cloned().unwrap_or_else(N::zero);
+            let kept = value.mul(&k);
+            if let Some(entry) = retained.get_mut(candidate) {
+                *entry = entry.add(&kept);
+            }
+            value = value.mul(&one.sub(&k));
+        }
+
+        excess = excess.add(&value);
+    }
+
+    (retained, excess)
+}
+
+/// Run the Meek STV count.
+pub fn meek_stv<N: Number>(
+    candidates: &[CandidateId],
+    ballots: &[WeightedBallot],
+    seats: u32,
+    tolerance: N,
+    constraints: Option<&Constraints>,
+) -> MeekResult {
+    let one = N::from_int(1);
+    let mut keep: BTreeMap<CandidateId, N> = candidates.iter().map(|c| (*c, one.clone())).collect();
+    let mut status: BTreeMap<CandidateId, Status> =
+        candidates.iter().map(|c| (*c, Status::Hopeful)).collect();
+
+    let total: N = ballots
+        .iter()
+        .fold(N::zero(), |acc, b| acc.add(&N::from_int(b.weight as i64)));
+
+    let mut stages: Vec<MeekStage> = Vec::new();
+
+    loop {
+        let elected_count = status.values().filter(|s| **s == Status::Elected).count() as u32;
+        let hopeful_count = status.values().filter(|s| **s == Status::Hopeful).count() as u32;
+
+        if elected_count >= seats || elected_count + hopeful_count <= seats {
+            // Fill any remaining seats with the hopefuls.
+            if elected_count < seats {
+                for (c, s) in status.iter_mut() {
+                    if *s == Status::Hopeful {
+                        *s = Status::Elected;
+                        let _ = c;
+                    }
+                }
+            }
+            break;
+        }
+
+        // Iterate keep-value recomputation until the elected candidates'
+        // retained votes converge on the quota.
+        let mut retained;
+        let mut quota;
+        let mut excess_out;
+        let seats_plus_one = N::from_int(seats as i64 + 1);
+        let mut iterations = 0;
+        loop {
+            let (r, excess) = pour(ballots, &keep, &status);
+            quota = total.sub(&excess).div(&seats_plus_one);
+            retained = r;
+            excess_out = excess;
+
+            let mut converged = true;
+            for (c, s) in &status {
+                if *s != Status::Elected {
+                    continue;
+                }
+                let votes = retained.get(c).cloned().unwrap_or_else(N::zero);
+                let diff = votes.sub(&quota);
+                let abs = if diff.cmp(&N::zero()) == std::cmp::Ordering::Less {
+                    N::zero().sub(&diff)
+                } else {
+                    diff
+                };
+                if abs.cmp(&tolerance) == std::cmp::Ordering::Greater {
+                    converged = false;
+                    // k_new = k_old * quota / votes_received
+                    if votes.cmp(&N::zero()) == std::cmp::Ordering::Greater {
+                        let k = keep.get(c).cloned().unwrap();
+                        keep.insert(*c, k.mul(&quota).div(&votes));
+                    }
+                }
+            }
+
+            iterations += 1;
+            if converged || iterations >= MAX_ITERATIONS {
+                break;
+            }
+        }
+
+        stages.push(MeekStage {
+            description: format!("Stage {}", stages.len() + 1),
+            totals: retained.iter().map(|(c, v)| (*c, v.to_f64())).collect(),
+            quota: quota.to_f64(),
+            excess: excess_out.to_f64(),
+        });
+
+        // Representation-constraint verdicts for this stage: a blocked hopeful
+        // sits in a category already at its cap and must not be elected; a
+        // guarded hopeful protects an under-filled category and must not be
+        // excluded.
+        let elected_ids: Vec<CandidateId> = status
+            .iter()
+            .filter(|(_, s)| **s == Status::Elected)
+            .map(|(c, _)| *c)
+            .collect();
+        let hopeful_ids: Vec<CandidateId> = status
+            .iter()
+            .filter(|(_, s)| **s == Status::Hopeful)
+            .map(|(c, _)| *c)
+            .collect();
+        let status_of = |c: CandidateId| -> GuardStatus {
+            constraints
+                .map(|k| k.status_in(c, &elected_ids, &hopeful_ids))
+                .unwrap_or(GuardStatus::Free)
+        };
+
+        // Elect any hopeful reaching quota that the constraints do not block.
+        let newly_elected: Vec<CandidateId> = status
+            .iter()
+            .filter(|(c, s)| {
+                **s == Status::Hopeful
+                    && status_of(**c) != GuardStatus::Blocked
+                    && retained
+                        .get(*c)
+                        .map(|v| v.cmp(&quota) != std::cmp::Ordering::Less)
+                        .unwrap_or(false)
+            })
+            .map(|(c, _)| *c)
+            .collect();
+
+        if !newly_elected.is_empty() {
+            for c in newly_elected {
+                status.insert(c, Status::Elected);
+            }
+            continue;
+        }
+
+        // No one electable reached quota: exclude a hopeful (keep value -> 0).
+        // A doomed (blocked) hopeful goes first; otherwise the lowest hopeful
+        // the constraints do not guard, falling back to the overall lowest when
+        // every hopeful is guarded.
+        let votes_of = |c: &CandidateId| retained.get(c).cloned().unwrap_or_else(N::zero);
+        let lowest_where = |keep_fn: &dyn Fn(CandidateId) -> bool| -> Option<CandidateId> {
+            status
+                .iter()
+                .filter(|(c, s)| **s == Status::Hopeful && keep_fn(**c))
+                .min_by(|a, b| votes_of(a.0).cmp(&votes_of(b.0)))
+                .map(|(c, _)| *c)
+        };
+        let lowest = lowest_where(&|c| status_of(c) == GuardStatus::Blocked)
+            .or_else(|| lowest_where(&|c| status_of(c) != GuardStatus::Guarded))
+            .or_else(|| lowest_where(&|_| true));
+
+        if let Some(c) = lowest {
+            status.insert(c, Status::Excluded);
+            keep.insert(c, N::zero());
+        } else {
+            break;
+        }
+    }
+
+    let elected: Vec<CandidateId> = status
+        .iter()
+        .filter(|(_, s)| **s == Status::Elected)
+        .map(|(c, _)| *c)
+        .collect();
+
+    MeekResult { elected, stages }
+}
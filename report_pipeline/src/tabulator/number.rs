@@ -0,0 +1,288 @@
+//! Pluggable exact-arithmetic number abstraction for surplus transfers.
+//!
+//! Plain integer ballot counts cannot represent the fractional surplus transfer
+//! values needed by modern STV rules. This module introduces a [`Number`] trait
+//! with three concrete implementations so callers can pick speed (float) versus
+//! reproducibility (rational / fixed-point) at the call site:
+//!
+//! - [`Fixed`]: guarded fixed-point with a configurable number of decimal
+//!   places, matching rules (e.g. Scottish STV) that truncate transfer values.
+//! - [`Rational`]: exact rational with bignum numerator/denominator, for counts
+//!   that must be reproducible bit-for-bit.
+//! - [`Float`]: a 64-bit float, for speed where exactness is not required.
+//!
+//! Meek's count is generic over `N: Number`, so it runs under any backend; the
+//! Scottish (Gregory) count runs on [`Fixed`].
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// Decimal places used by the fixed-point backend when a precision is not
+/// supplied explicitly — the Scottish STV truncation precision. Shared between
+/// [`NumberKind`] and [`Fixed`] so there is a single source of truth.
+pub(crate) const DEFAULT_FIXED_PLACES: u32 = 5;
+
+/// The arithmetic required by the STV tallying code.
+///
+/// Implementors provide the usual field operations plus `from_int`, comparison,
+/// and `floor_to_places`, used when truncating transfer values.
+pub trait Number: Clone + Debug + PartialEq {
+    fn from_int(value: i64) -> Self;
+    fn zero() -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    fn div(&self, other: &Self) -> Self;
+
+    fn cmp(&self, other: &Self) -> Ordering;
+
+    /// Round down to `places` decimal places, as required by rules that
+    /// truncate (rather than round) transfer values.
+    fn floor_to_places(&self, places: u32) -> Self;
+
+    /// Best-effort conversion to `f64` for display and reporting.
+    fn to_f64(&self) -> f64;
+}
+
+/// Which numeric backend a contest declares, parsed from the `numbers`
+/// tabulation option. It sets the precision disclosed in report output; it is
+/// not passed to `tabulate`/`total_votes` or to the STV counts, which count in
+/// their own fixed representations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    /// 64-bit float: fast, not reproducible.
+    Float,
+    /// Fixed-point decimal truncated to the given number of places.
+    Fixed { places: u32 },
+    /// Exact rational: fully reproducible.
+    Rational,
+}
+
+impl Default for NumberKind {
+    fn default() -> NumberKind {
+        NumberKind::Float
+    }
+}
+
+impl NumberKind {
+    /// Default decimal places for fixed-point when the option omits a count.
+    const DEFAULT_PLACES: u32 = DEFAULT_FIXED_PLACES;
+
+    /// Parse the `numbers` tabulation option, e.g. `float`, `rational`,
+    /// `fixed`, or `fixed:6`. Unknown values fall back to [`NumberKind::Float`].
+    pub fn from_option(value: Option<&String>) -> NumberKind {
+        match value.map(|s| s.to_lowercase()).as_deref() {
+            Some("rational") | Some("exact") => NumberKind::Rational,
+            Some(fixed) if fixed.starts_with("fixed") => {
+                let places = fixed
+                    .split_once(':')
+                    .and_then(|(_, p)| p.parse().ok())
+                    .unwrap_or(Self::DEFAULT_PLACES);
+                NumberKind::Fixed { places }
+            }
+            _ => NumberKind::Float,
+        }
+    }
+
+    /// The decimal precision to disclose in report output: `None` for exact
+    /// rationals (which carry no fixed precision), the place count otherwise.
+    pub fn reported_precision(&self) -> Option<u32> {
+        match self {
+            NumberKind::Float => Some(15),
+            NumberKind::Fixed { places } => Some(*places),
+            NumberKind::Rational => None,
+        }
+    }
+}
+
+/// A 64-bit float backend. Fast, but subject to rounding error.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Float(pub f64);
+
+impl Number for Float {
+    fn from_int(value: i64) -> Self {
+        Float(value as f64)
+    }
+    fn zero() -> Self {
+        Float(0.0)
+    }
+    fn add(&self, other: &Self) -> Self {
+        Float(self.0 + other.0)
+    }
+    fn sub(&self, other: &Self) -> Self {
+        Float(self.0 - other.0)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Float(self.0 * other.0)
+    }
+    fn div(&self, other: &Self) -> Self {
+        Float(self.0 / other.0)
+    }
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+    fn floor_to_places(&self, places: u32) -> Self {
+        let scale = 10f64.powi(places as i32);
+        Float((self.0 * scale).floor() / scale)
+    }
+    fn to_f64(&self) -> f64 {
+        self.0
+    }
+}
+
+/// An exact rational backend with bignum numerator and denominator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rational(pub BigRational);
+
+impl Rational {
+    /// The exact value rendered as `numerator/denominator`, for audit output
+    /// where an f64 approximation would hide a reproducible transfer value.
+    pub fn to_exact_string(&self) -> String {
+        format!("{}/{}", self.0.numer(), self.0.denom())
+    }
+}
+
+impl Number for Rational {
+    fn from_int(value: i64) -> Self {
+        Rational(BigRational::from_integer(BigInt::from(value)))
+    }
+    fn zero() -> Self {
+        Rational(BigRational::zero())
+    }
+    fn add(&self, other: &Self) -> Self {
+        Rational(&self.0 + &other.0)
+    }
+    fn sub(&self, other: &Self) -> Self {
+        Rational(&self.0 - &other.0)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Rational(&self.0 * &other.0)
+    }
+    fn div(&self, other: &Self) -> Self {
+        Rational(&self.0 / &other.0)
+    }
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+    fn floor_to_places(&self, places: u32) -> Self {
+        let scale = BigRational::from_integer(BigInt::from(10).pow(places));
+        let scaled = (&self.0 * &scale).floor();
+        Rational(scaled / scale)
+    }
+    fn to_f64(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+/// A guarded fixed-point backend carrying a value scaled by `10^places`.
+///
+/// Arithmetic is performed on the underlying scaled integer and re-truncated to
+/// `places` decimal places, so transfer values stay reproducible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixed {
+    /// The value multiplied by `10^places`.
+    scaled: BigInt,
+    /// The number of decimal places retained.
+    places: u32,
+}
+
+impl Fixed {
+    /// A zero value carrying `places` decimal places.
+    pub fn new(places: u32) -> Fixed {
+        Fixed {
+            scaled: BigInt::zero(),
+            places,
+        }
+    }
+
+    fn scale(&self) -> BigInt {
+        BigInt::from(10).pow(self.places)
+    }
+
+    fn with_int(value: i64, places: u32) -> Fixed {
+        let scaled = BigInt::from(value) * BigInt::from(10).pow(places);
+        Fixed { scaled, places }
+    }
+
+    /// This value's scaled integer re-expressed at `places` decimal places.
+    ///
+    /// Two fixed-point values can carry different precisions — e.g. a quota seeded
+    /// at `fixed:6` combined with a ballot weight from [`Number::from_int`] at the
+    /// default precision. Operating on the raw `scaled` integers directly would
+    /// add mismatched scales; every binary operation first rescales both operands
+    /// to their common (larger) precision.
+    fn rescaled(&self, places: u32) -> BigInt {
+        match places.cmp(&self.places) {
+            Ordering::Equal => self.scaled.clone(),
+            Ordering::Greater => &self.scaled * BigInt::from(10).pow(places - self.places),
+            Ordering::Less => &self.scaled / BigInt::from(10).pow(self.places - places),
+        }
+    }
+}
+
+impl Number for Fixed {
+    fn from_int(value: i64) -> Self {
+        // Seeds at the default precision; a value combined with a higher-precision
+        // operand (e.g. one started from `Fixed::new(6)`) is rescaled up to that
+        // precision by the arithmetic below.
+        Fixed::with_int(value, DEFAULT_FIXED_PLACES)
+    }
+    fn zero() -> Self {
+        Fixed::new(DEFAULT_FIXED_PLACES)
+    }
+    fn add(&self, other: &Self) -> Self {
+        let places = self.places.max(other.places);
+        Fixed {
+            scaled: self.rescaled(places) + other.rescaled(places),
+            places,
+        }
+    }
+    fn sub(&self, other: &Self) -> Self {
+        let places = self.places.max(other.places);
+        Fixed {
+            scaled: self.rescaled(places) - other.rescaled(places),
+            places,
+        }
+    }
+    fn mul(&self, other: &Self) -> Self {
+        // (a/scale) * (b/scale) = a*b/scale^2, so divide out one scale factor
+        // and truncate, as fixed-point STV rules require.
+        let places = self.places.max(other.places);
+        let product = self.rescaled(places) * other.rescaled(places);
+        Fixed {
+            scaled: product / BigInt::from(10).pow(places),
+            places,
+        }
+    }
+    fn div(&self, other: &Self) -> Self {
+        let places = self.places.max(other.places);
+        let numerator = self.rescaled(places) * BigInt::from(10).pow(places);
+        Fixed {
+            scaled: numerator / other.rescaled(places),
+            places,
+        }
+    }
+    fn cmp(&self, other: &Self) -> Ordering {
+        let places = self.places.max(other.places);
+        self.rescaled(places).cmp(&other.rescaled(places))
+    }
+    fn floor_to_places(&self, places: u32) -> Self {
+        if places >= self.places {
+            return self.clone();
+        }
+        let divisor = BigInt::from(10).pow(self.places - places);
+        let truncated = (&self.scaled / &divisor) * &divisor;
+        Fixed {
+            scaled: truncated,
+            places: self.places,
+        }
+    }
+    fn to_f64(&self) -> f64 {
+        let scale = self.scale().to_f64().unwrap_or(1.0);
+        self.scaled.to_f64().unwrap_or(f64::NAN) / scale
+    }
+}
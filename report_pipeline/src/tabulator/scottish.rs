@@ -0,0 +1,209 @@
+//! Scottish local-government STV (Weighted Inclusive Gregory).
+//!
+//! This backend runs the Scottish STV rules over the preference orders built
+//! from an `Election`. The Droop quota is `floor(valid_ballots / (seats + 1)) + 1`.
+//! In each stage every active ballot is assigned to its highest
+//! non-excluded, non-elected preference; a candidate meeting quota is elected,
+//! and their surplus is transferred by the Weighted Inclusive Gregory method:
+//! `transfer_value = surplus / (total weight of that candidate's ballots)`, with
+//! each transferable ballot's carried weight multiplied by that value and
+//! **truncated to 5 decimal places** (see [`Fixed`]). Non-transferable ballots
+//! go to exhausted. When no candidate reaches quota, the lowest candidate is
+//! excluded and all their ballots are transferred at their current weight.
+//!
+//! Per-stage vote totals are recorded so the result is auditable against
+//! official Scottish counts.
+
+use super::constraints::{Constraints, GuardStatus};
+use super::number::{Fixed, Number};
+use crate::model::election::CandidateId;
+use std::collections::BTreeMap;
+
+/// The precision (decimal places) to which Gregory transfer values are
+/// truncated under the Scottish rules.
+const TRANSFER_PLACES: u32 = 5;
+
+/// A single ballot: a weighted preference order.
+pub struct WeightedBallot {
+    pub preferences: Vec<CandidateId>,
+    pub weight: u32,
+}
+
+/// One stage of the count, with each continuing candidate's vote total.
+#[derive(Debug, Clone)]
+pub struct ScottishStage {
+    pub description: String,
+    pub totals: BTreeMap<CandidateId, f64>,
+    pub exhausted: f64,
+}
+
+/// The outcome of a Scottish STV count.
+#[derive(Debug, Clone)]
+pub struct ScottishResult {
+    pub elected: Vec<CandidateId>,
+    pub quota: f64,
+    pub stages: Vec<ScottishStage>,
+}
+
+/// Per-ballot running state during the count.
+struct BallotState {
+    preferences: Vec<CandidateId>,
+    /// The carried weight of this ballot, scaled by the original multiplicity.
+    value: Fixed,
+    /// Index into `preferences` of the candidate currently holding this ballot.
+    position: usize,
+}
+
+/// Run the Scottish STV (WIG) count.
+pub fn scottish_stv(
+    candidates: &[CandidateId],
+    ballots: &[WeightedBallot],
+    seats: u32,
+    constraints: Option<&Constraints>,
+) -> ScottishResult {
+    let fixed = |n: i64| Fixed::from_int(n).floor_to_places(TRANSFER_PLACES);
+
+    // Expand multiplicities into carried-weight ballot state.
+    let mut states: Vec<BallotState> = ballots
+        .iter()
+        .filter(|b| !b.preferences.is_empty())
+        .map(|b| BallotState {
+            preferences: b.preferences.clone(),
+            value: fixed(b.weight as i64),
+            position: 0,
+        })
+        .collect();
+
+    let valid_ballots: i64 = ballots
+        .iter()
+        .filter(|b| !b.preferences.is_empty())
+        .map(|b| b.weight as i64)
+        .sum();
+    let quota = fixed(valid_ballots / (seats as i64 + 1) + 1);
+
+    let mut elected: Vec<CandidateId> = Vec::new();
+    let mut excluded: Vec<CandidateId> = Vec::new();
+    let mut stages: Vec<ScottishStage> = Vec::new();
+
+    loop {
+        // Advance each ballot to its highest continuing preference.
+        for state in states.iter_mut() {
+            while state.position < state.preferences.len() {
+                let c = state.preferences[state.position];
+                if elected.contains(&c) || excluded.contains(&c) {
+                    state.position += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Tally continuing candidates.
+        let mut totals: BTreeMap<CandidateId, Fixed> = candidates
+            .iter()
+            .filter(|c| !elected.contains(c) && !excluded.contains(c))
+            .map(|c| (*c, Fixed::new(TRANSFER_PLACES)))
+            .collect();
+        let mut exhausted = Fixed::new(TRANSFER_PLACES);
+        for state in &states {
+            match state.preferences.get(state.position) {
+                Some(c) if totals.contains_key(c) => {
+                    let entry = totals.get_mut(c).unwrap();
+                    *entry = entry.add(&state.value);
+                }
+                _ => exhausted = exhausted.add(&state.value),
+            }
+        }
+
+        stages.push(ScottishStage {
+            description: format!("Stage {}", stages.len() + 1),
+            totals: totals.iter().map(|(c, v)| (*c, v.to_f64())).collect(),
+            exhausted: exhausted.to_f64(),
+        });
+
+        if elected.len() as u32 >= seats {
+            break;
+        }
+
+        let continuing: Vec<CandidateId> = totals.keys().copied().collect();
+        if continuing.len() as u32 + elected.len() as u32 <= seats {
+            // Fill the remaining seats with all remaining continuing candidates.
+            elected.extend(continuing);
+            break;
+        }
+
+        // Representation-constraint verdicts for the candidates still standing:
+        // a blocked candidate sits in a category already at its cap and must not
+        // be elected, and a guarded candidate protects an under-filled category
+        // and must not be excluded.
+        let statuses: BTreeMap<CandidateId, GuardStatus> = continuing
+            .iter()
+            .map(|c| {
+                let status = constraints
+                    .map(|k| k.status_in(*c, &elected, &continuing))
+                    .unwrap_or(GuardStatus::Free);
+                (*c, status)
+            })
+            .collect();
+        let status_of =
+            |c: &CandidateId| statuses.get(c).copied().unwrap_or(GuardStatus::Free);
+
+        // Elect the largest surplus among candidates meeting quota that the
+        // constraints do not block, transferring that surplus first.
+        let over_quota: Option<CandidateId> = totals
+            .iter()
+            .filter(|(c, v)| {
+                v.cmp(&quota) != std::cmp::Ordering::Less
+                    && status_of(c) != GuardStatus::Blocked
+            })
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(c, _)| *c);
+
+        if let Some(winner) = over_quota {
+            elected.push(winner);
+            let winner_total = totals.get(&winner).unwrap().clone();
+            let surplus = winner_total.sub(&quota);
+
+            // Weighted Inclusive Gregory: transfer value applied to every ballot
+            // currently held by the winner, truncated to 5 decimal places.
+            let transfer_value = surplus.div(&winner_total).floor_to_places(TRANSFER_PLACES);
+            for state in states.iter_mut() {
+                if state.preferences.get(state.position) == Some(&winner) {
+                    state.value = state.value.mul(&transfer_value).floor_to_places(TRANSFER_PLACES);
+                }
+            }
+            continue;
+        }
+
+        // Nobody electable reached quota. Exclude a doomed (blocked) candidate
+        // first; otherwise the lowest candidate the constraints do not guard,
+        // falling back to the overall lowest if every one standing is guarded.
+        // The excluded candidate's ballots transfer at full current weight.
+        let doomed = totals
+            .iter()
+            .filter(|(c, _)| status_of(c) == GuardStatus::Blocked)
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(c, _)| *c);
+        let lowest = doomed
+            .or_else(|| {
+                totals
+                    .iter()
+                    .filter(|(c, _)| status_of(c) != GuardStatus::Guarded)
+                    .min_by(|a, b| a.1.cmp(b.1))
+                    .map(|(c, _)| *c)
+            })
+            .or_else(|| totals.iter().min_by(|a, b| a.1.cmp(b.1)).map(|(c, _)| *c));
+
+        if let Some(lowest) = lowest {
+            excluded.push(lowest);
+        } else {
+            break;
+        }
+    }
+
+    ScottishResult {
+        elected,
+        quota: quota.to_f64(),
+        stages,
+    }
+}
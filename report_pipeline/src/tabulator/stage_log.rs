@@ -0,0 +1,136 @@
+//! Structured per-stage tabulation log.
+//!
+//! `ContestReport` keeps the raw [`TabulatorRound`]s, but consumers that want
+//! to render a step-by-step breakdown (a Sankey diagram, an auditor's recount
+//! sheet) need each counting step described explicitly: what kind of step it
+//! was, every candidate's running total and the change from the previous step,
+//! the quota in force, the exhausted/non-transferable pile, and any tie-break
+//! or constraint note. This module distills the rounds into that form so it can
+//! be serialized alongside `rounds` in `report.json`.
+
+use super::{Allocatee, TabulatorRound};
+use crate::model::election::CandidateId;
+
+/// The kind of counting step a stage represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageKind {
+    /// The opening distribution of first preferences.
+    FirstPreferences,
+    /// A surplus from a candidate who reached quota was redistributed.
+    SurplusTransfer,
+    /// The lowest candidate was excluded and their votes transferred.
+    Elimination,
+}
+
+impl StageKind {
+    /// A short human-readable label for the stage.
+    pub fn title(&self) -> &'static str {
+        match self {
+            StageKind::FirstPreferences => "First preferences",
+            StageKind::SurplusTransfer => "Surplus transfer",
+            StageKind::Elimination => "Elimination",
+        }
+    }
+}
+
+/// A single candidate's line within a stage.
+#[derive(Debug, Clone)]
+pub struct StageCandidate {
+    pub candidate: CandidateId,
+    /// Running total after this stage.
+    pub votes: u32,
+    /// Change from the previous stage (can be negative when votes leave).
+    pub delta: i64,
+}
+
+/// One step of the count.
+#[derive(Debug, Clone)]
+pub struct StageRecord {
+    /// 1-based stage number.
+    pub stage: u32,
+    pub kind: StageKind,
+    /// Human-readable title, e.g. "Stage 3: Elimination".
+    pub title: String,
+    pub candidates: Vec<StageCandidate>,
+    /// The quota in effect, if the count uses one.
+    pub quota: Option<u32>,
+    /// Exhausted / non-transferable votes at this stage.
+    pub exhausted: u32,
+    /// A note about a tie-break or constraint action taken this stage.
+    pub note: Option<String>,
+}
+
+/// The running candidate totals and exhausted pile for a round.
+fn totals(round: &TabulatorRound) -> (Vec<(CandidateId, u32)>, u32) {
+    let mut candidates = Vec::new();
+    let mut exhausted = 0;
+    for alloc in &round.allocations {
+        match alloc.allocatee {
+            Allocatee::Candidate(c) => candidates.push((c, alloc.votes)),
+            Allocatee::Exhausted => exhausted = alloc.votes,
+        }
+    }
+    (candidates, exhausted)
+}
+
+/// Build the per-stage log from the tabulator rounds.
+///
+/// `quota` is threaded through so multi-seat counts can disclose the quota that
+/// governed each stage; pass `None` for single-winner IRV. The stage kind is
+/// inferred from whether a round records a transfer away from a candidate
+/// (elimination) versus carrying a surplus, with the opening round always being
+/// first preferences.
+pub fn build_stage_log(rounds: &[TabulatorRound], quota: Option<u32>) -> Vec<StageRecord> {
+    let mut log = Vec::with_capacity(rounds.len());
+    let mut previous: Vec<(CandidateId, u32)> = Vec::new();
+
+    for (index, round) in rounds.iter().enumerate() {
+        let (current, exhausted) = totals(round);
+
+        let kind = if index == 0 {
+            StageKind::FirstPreferences
+        } else if round.transfers.iter().any(|t| {
+            // A transfer away from a candidate whose total fell to zero is an
+            // exclusion; otherwise the candidate kept a seat and shed a surplus.
+            current
+                .iter()
+                .find(|(c, _)| *c == t.from)
+                .map(|(_, v)| *v == 0)
+                .unwrap_or(true)
+        }) {
+            StageKind::Elimination
+        } else {
+            StageKind::SurplusTransfer
+        };
+
+        let candidates = current
+            .iter()
+            .map(|(candidate, votes)| {
+                let prev = previous
+                    .iter()
+                    .find(|(c, _)| c == candidate)
+                    .map(|(_, v)| *v)
+                    .unwrap_or(0);
+                StageCandidate {
+                    candidate: *candidate,
+                    votes: *votes,
+                    delta: *votes as i64 - prev as i64,
+                }
+            })
+            .collect();
+
+        log.push(StageRecord {
+            stage: (index + 1) as u32,
+            kind,
+            title: format!("Stage {}: {}", index + 1, kind.title()),
+            candidates,
+            quota,
+            exhausted,
+            note: None,
+        });
+
+        previous = current;
+    }
+
+    log
+}
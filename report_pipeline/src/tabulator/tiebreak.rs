@@ -0,0 +1,185 @@
+//! Configurable, auditable tie-breaking.
+//!
+//! Several points in a count can tie: two candidates sharing the equal-lowest
+//! total when choosing who to eliminate, or two candidates with an equal
+//! surplus when ordering elections. Left implicit these ties make the output
+//! non-deterministic and opaque, so this module makes the policy explicit and
+//! records, for every tie it resolves, which strategy decided it and why.
+//!
+//! Three strategies are offered, selected per contest via a `tie_break` entry
+//! in `loader_params`:
+//!
+//! - **Backwards** (the default) compares the tied candidates by their vote
+//!   totals in the latest prior round in which they differed, recursing back
+//!   towards first preferences until the tie is broken.
+//! - **Forwards** is the mirror image, comparing from the earliest round.
+//! - **Random** selects deterministically from a contest-supplied seed, so a
+//!   genuinely irreducible tie (identical in every round) still resolves the
+//!   same way on every recount.
+
+use super::{Allocatee, TabulatorRound};
+use crate::model::election::CandidateId;
+
+/// How ties are resolved in a contest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare tied candidates by their totals in the latest differing round.
+    Backwards,
+    /// Compare tied candidates by their totals in the earliest differing round.
+    Forwards,
+    /// Deterministic pseudo-random selection seeded from the contest.
+    Random { seed: u64 },
+}
+
+impl Default for TieBreak {
+    fn default() -> TieBreak {
+        TieBreak::Backwards
+    }
+}
+
+impl TieBreak {
+    /// Parse the `tie_break` tabulation option, defaulting to backwards.
+    ///
+    /// A `random` policy reads its seed from the companion `tie_break_seed`
+    /// option, falling back to `0` so the selection is still reproducible.
+    pub fn from_options(
+        tie_break: Option<&String>,
+        tie_break_seed: Option<&String>,
+    ) -> TieBreak {
+        match tie_break.map(|s| s.to_lowercase()).as_deref() {
+            Some("forwards") | Some("forward") => TieBreak::Forwards,
+            Some("random") => {
+                let seed = tie_break_seed.and_then(|s| s.parse().ok()).unwrap_or(0);
+                TieBreak::Random { seed }
+            }
+            _ => TieBreak::Backwards,
+        }
+    }
+}
+
+/// A record of a single tie and how it was resolved, for inclusion in the
+/// report so auditors can see exactly what happened.
+#[derive(Debug, Clone)]
+pub struct TieBreakRecord {
+    /// The candidates that were tied.
+    pub tied: Vec<CandidateId>,
+    /// The candidate the strategy selected (for elimination or election).
+    pub selected: CandidateId,
+    /// The strategy that made the decision.
+    pub strategy: TieBreak,
+    /// A human-readable account of how the tie broke, e.g. which round
+    /// separated the candidates or that the seed was consulted.
+    pub reason: String,
+}
+
+impl TieBreakRecord {
+    /// A compact note suitable for attaching to the [`TabulatorRound`] that
+    /// invoked the tie-break, so the report discloses the strategy and the
+    /// candidates involved alongside the round it decided.
+    pub fn round_note(&self) -> String {
+        let strategy = match self.strategy {
+            TieBreak::Backwards => "backwards",
+            TieBreak::Forwards => "forwards",
+            TieBreak::Random { .. } => "random",
+        };
+        format!(
+            "tie among {} candidates resolved ({}): {}",
+            self.tied.len(),
+            strategy,
+            self.reason
+        )
+    }
+}
+
+/// The per-candidate total in `round`, treating an absent candidate as zero.
+fn total_in(round: &TabulatorRound, candidate: CandidateId) -> u32 {
+    round
+        .allocations
+        .iter()
+        .find(|a| a.allocatee == Allocatee::Candidate(candidate))
+        .map(|a| a.votes)
+        .unwrap_or(0)
+}
+
+/// Resolve a tie between `tied` candidates, picking the one to act on (the
+/// lowest total is eliminated, so callers pass the candidates sharing the
+/// equal-lowest total and receive the one to exclude).
+///
+/// `rounds` is every round counted so far, oldest first. The returned record
+/// documents the decision for the report.
+pub fn break_tie(
+    tied: &[CandidateId],
+    rounds: &[TabulatorRound],
+    strategy: &TieBreak,
+) -> TieBreakRecord {
+    debug_assert!(tied.len() >= 2, "break_tie called without a tie");
+
+    // Iterate prior rounds in the direction the strategy dictates, selecting
+    // the candidate with the lowest total in the first round that separates
+    // them. `Backwards` walks latest-first; `Forwards` earliest-first.
+    let order: Vec<usize> = match strategy {
+        TieBreak::Backwards => (0..rounds.len()).rev().collect(),
+        TieBreak::Forwards => (0..rounds.len()).collect(),
+        TieBreak::Random { .. } => Vec::new(),
+    };
+
+    for round_index in order {
+        let round = &rounds[round_index];
+        let mut lowest = tied[0];
+        let mut lowest_votes = total_in(round, tied[0]);
+        let mut unique = true;
+
+        for &candidate in &tied[1..] {
+            let votes = total_in(round, candidate);
+            if votes < lowest_votes {
+                lowest = candidate;
+                lowest_votes = votes;
+                unique = true;
+            } else if votes == lowest_votes {
+                unique = false;
+            }
+        }
+
+        if unique {
+            return TieBreakRecord {
+                tied: tied.to_vec(),
+                selected: lowest,
+                strategy: strategy.clone(),
+                reason: format!(
+                    "separated at round {} ({} votes)",
+                    round_index + 1,
+                    lowest_votes
+                ),
+            };
+        }
+    }
+
+    // Either the random strategy was selected or every round was identical;
+    // fall back to a seeded deterministic choice so recounts reproduce.
+    let seed = match strategy {
+        TieBreak::Random { seed } => *seed,
+        _ => 0,
+    };
+    let selected = seeded_pick(tied, seed);
+    TieBreakRecord {
+        tied: tied.to_vec(),
+        selected,
+        strategy: strategy.clone(),
+        reason: format!("irreducible tie resolved by seed {}", seed),
+    }
+}
+
+/// Deterministically pick one candidate from `tied` using `seed`. The tied
+/// candidates are ordered by id first so the choice does not depend on the
+/// order the caller happened to collect them in.
+fn seeded_pick(tied: &[CandidateId], seed: u64) -> CandidateId {
+    let mut ordered = tied.to_vec();
+    ordered.sort();
+    // A splitmix64 step gives a well-distributed index from the seed without
+    // pulling in an RNG dependency; the count is small so bias is negligible.
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    ordered[(z % ordered.len() as u64) as usize]
+}